@@ -191,6 +191,35 @@ pub fn build() {
   }
 }
 
+/// Reruns the build script when the current git commit changes.
+fn rerun_if_git_head_changed() {
+  let git_dir = match std::process::Command::new("git")
+    .args(["rev-parse", "--git-dir"])
+    .output()
+  {
+    Ok(output) if output.status.success() => {
+      PathBuf::from(String::from_utf8_lossy(&output.stdout).trim())
+    }
+    _ => return,
+  };
+
+  let head = git_dir.join("HEAD");
+  println!("cargo:rerun-if-changed={}", head.display());
+  if let Some(reference) = std::fs::read_to_string(&head)
+    .ok()
+    .and_then(|head| head.strip_prefix("ref:").map(|r| r.trim().to_string()))
+  {
+    let reference = git_dir.join(reference);
+    if reference.exists() {
+      println!("cargo:rerun-if-changed={}", reference.display());
+    }
+  }
+  let packed_refs = git_dir.join("packed-refs");
+  if packed_refs.exists() {
+    println!("cargo:rerun-if-changed={}", packed_refs.display());
+  }
+}
+
 /// Non-panicking [`build()`].
 #[allow(unused_variables)]
 pub fn try_build(attributes: Attributes) -> Result<()> {
@@ -217,6 +246,16 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
     let merge_config: serde_json::Value = serde_json::from_str(&env)?;
     json_patch::merge(&mut config, &merge_config);
   }
+  let substituted = tauri_utils::config::parse::substitute_variables(
+    &mut config,
+    std::env::var("CARGO_PKG_VERSION").ok().as_deref(),
+  )?;
+  for env in substituted.env {
+    println!("cargo:rerun-if-env-changed={}", env);
+  }
+  if substituted.git_hash {
+    rerun_if_git_head_changed();
+  }
   let config: Config = serde_json::from_value(config)?;
 
   cfg_alias("dev", !has_feature("custom-protocol"));
@@ -301,7 +340,11 @@ pub fn try_build(attributes: Attributes) -> Result<()> {
   {
     resources.push(fixed_webview2_runtime_path.display().to_string());
   }
-  copy_resources(ResourcePaths::new(resources.as_slice(), true), target_dir)?;
+  let mut resource_paths = ResourcePaths::new(resources.as_slice(), true);
+  if let Some(exclude_resources) = &config.tauri.bundle.exclude_resources {
+    resource_paths = resource_paths.exclude(exclude_resources);
+  }
+  copy_resources(resource_paths, target_dir)?;
 
   #[cfg(target_os = "macos")]
   {
@@ -63,6 +63,10 @@ pub fn get_config(path: &Path) -> Result<(Config, PathBuf), CodegenConfigError>
       serde_json::from_str(&env).map_err(CodegenConfigError::FormatInline)?;
     json_patch::merge(&mut config, &merge_config);
   }
+  tauri_utils::config::parse::substitute_variables(
+    &mut config,
+    std::env::var("CARGO_PKG_VERSION").ok().as_deref(),
+  )?;
 
   let old_cwd = std::env::current_dir().map_err(CodegenConfigError::CurrentDir)?;
   // Set working directory to where `tauri.config.json` is, so that relative paths in it are parsed correctly.
@@ -1099,6 +1099,8 @@ pub enum TrayMessage {
   UpdateIconAsTemplate(bool),
   #[cfg(target_os = "macos")]
   UpdateTitle(String),
+  #[cfg(not(target_os = "linux"))]
+  UpdateTooltip(String),
   Create(SystemTray, Sender<Result<()>>),
   Destroy(Sender<Result<()>>),
 }
@@ -2573,6 +2575,12 @@ fn handle_user_message<T: UserEvent>(
               tray.set_title(&title);
             }
           }
+          #[cfg(not(target_os = "linux"))]
+          TrayMessage::UpdateTooltip(tooltip) => {
+            if let Some(tray) = &mut *tray_context.tray.lock().unwrap() {
+              tray.set_tooltip(&tooltip);
+            }
+          }
           TrayMessage::Create(_tray, _tx) => {
             // already handled
           }
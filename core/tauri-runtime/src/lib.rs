@@ -52,6 +52,7 @@ pub struct SystemTray {
   pub menu_on_left_click: bool,
   #[cfg(target_os = "macos")]
   pub title: Option<String>,
+  pub tooltip: Option<String>,
   pub on_event: Option<Box<TrayEventHandler>>,
 }
 
@@ -61,7 +62,8 @@ impl fmt::Debug for SystemTray {
     let mut d = f.debug_struct("SystemTray");
     d.field("id", &self.id)
       .field("icon", &self.icon)
-      .field("menu", &self.menu);
+      .field("menu", &self.menu)
+      .field("tooltip", &self.tooltip);
     #[cfg(target_os = "macos")]
     {
       d.field("icon_as_template", &self.icon_as_template)
@@ -79,6 +81,7 @@ impl Clone for SystemTray {
       id: self.id,
       icon: self.icon.clone(),
       menu: self.menu.clone(),
+      tooltip: self.tooltip.clone(),
       on_event: None,
       #[cfg(target_os = "macos")]
       icon_as_template: self.icon_as_template,
@@ -103,6 +106,7 @@ impl Default for SystemTray {
       menu_on_left_click: false,
       #[cfg(target_os = "macos")]
       title: None,
+      tooltip: None,
       on_event: None,
     }
   }
@@ -156,6 +160,13 @@ impl SystemTray {
     self
   }
 
+  /// Sets the tray icon tooltip.
+  #[must_use]
+  pub fn with_tooltip(mut self, tooltip: &str) -> Self {
+    self.tooltip = Some(tooltip.to_owned());
+    self
+  }
+
   /// Sets the menu to show when the system tray is right clicked.
   #[must_use]
   pub fn with_menu(mut self, menu: menu::SystemTrayMenu) -> Self {
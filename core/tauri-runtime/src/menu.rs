@@ -154,6 +154,10 @@ pub trait TrayHandle: fmt::Debug + Clone + Send + Sync {
   fn set_icon_as_template(&self, is_template: bool) -> crate::Result<()>;
   #[cfg(target_os = "macos")]
   fn set_title(&self, title: &str) -> crate::Result<()>;
+  /// Sets the tooltip of the tray icon. Does nothing unless the runtime supports tray tooltips.
+  fn set_tooltip(&self, _tooltip: &str) -> crate::Result<()> {
+    Ok(())
+  }
   fn destroy(&self) -> crate::Result<()>;
 }
 
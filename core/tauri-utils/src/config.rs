@@ -74,14 +74,28 @@ impl Default for WindowUrl {
 pub enum BundleType {
   /// The debian bundle (.deb).
   Deb,
+  /// The RPM bundle (.rpm).
+  Rpm,
+  /// The Flatpak bundle (.flatpak).
+  Flatpak,
+  /// The Snap package (.snap).
+  Snap,
   /// The AppImage bundle (.appimage).
   AppImage,
   /// The Microsoft Installer bundle (.msi).
   Msi,
+  /// The NSIS installer bundle (.exe).
+  Nsis,
+  /// The MSIX package bundle (.msix).
+  Msix,
+  /// The Windows portable zip bundle (.zip).
+  Portable,
   /// The macOS application bundle (.app).
   App,
   /// The Apple Disk Image bundle (.dmg).
   Dmg,
+  /// The macOS installer package (.pkg).
+  Pkg,
   /// The Tauri updater bundle.
   Updater,
 }
@@ -93,10 +107,17 @@ impl Display for BundleType {
       "{}",
       match self {
         Self::Deb => "deb",
+        Self::Rpm => "rpm",
+        Self::Flatpak => "flatpak",
+        Self::Snap => "snap",
         Self::AppImage => "appimage",
         Self::Msi => "msi",
+        Self::Nsis => "nsis",
+        Self::Msix => "msix",
+        Self::Portable => "portable",
         Self::App => "app",
         Self::Dmg => "dmg",
+        Self::Pkg => "pkg",
         Self::Updater => "updater",
       }
     )
@@ -120,10 +141,17 @@ impl<'de> Deserialize<'de> for BundleType {
     let s = String::deserialize(deserializer)?;
     match s.to_lowercase().as_str() {
       "deb" => Ok(Self::Deb),
+      "rpm" => Ok(Self::Rpm),
+      "flatpak" => Ok(Self::Flatpak),
+      "snap" => Ok(Self::Snap),
       "appimage" => Ok(Self::AppImage),
       "msi" => Ok(Self::Msi),
+      "nsis" => Ok(Self::Nsis),
+      "msix" => Ok(Self::Msix),
+      "portable" => Ok(Self::Portable),
       "app" => Ok(Self::App),
       "dmg" => Ok(Self::Dmg),
+      "pkg" => Ok(Self::Pkg),
       "updater" => Ok(Self::Updater),
       _ => Err(DeError::custom(format!("unknown bundle target '{}'", s))),
     }
@@ -243,6 +271,7 @@ impl BundleTarget {
 }
 
 /// Configuration for AppImage bundles.
+#[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
 #[serde(rename_all = "camelCase", deny_unknown_fields)]
@@ -251,6 +280,11 @@ pub struct AppImageConfig {
   /// This increases the bundle size by ~15-35MB depending on your build system.
   #[serde(default, alias = "bundle-media-framework")]
   pub bundle_media_framework: bool,
+  /// The update information to embed in the AppImage, e.g. `gh-releases-zsync|user|repo|latest|*.AppImage.zsync`.
+  /// When set, a `.zsync` file is generated next to the AppImage so it can be updated with AppImageUpdate.
+  /// See <https://github.com/AppImage/AppImageSpec/blob/master/draft.md#update-information>.
+  #[serde(alias = "update-information")]
+  pub update_information: Option<String>,
 }
 
 /// Configuration for Debian (.deb) bundles.
@@ -264,6 +298,125 @@ pub struct DebConfig {
   /// The files to include on the package.
   #[serde(default)]
   pub files: HashMap<PathBuf, PathBuf>,
+  /// The package maintainer, e.g. `Jane Doe <jane@example.com>`. Defaults to the package authors.
+  pub maintainer: Option<String>,
+  /// The application area the package belongs to, e.g. `utils` or `web`.
+  /// See <https://www.debian.org/doc/debian-policy/ch-archive.html#s-subsections>.
+  pub section: Option<String>,
+  /// The package priority. Defaults to `optional`.
+  /// See <https://www.debian.org/doc/debian-policy/ch-archive.html#s-priorities>.
+  pub priority: Option<String>,
+}
+
+/// Configuration for RPM bundles.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct RpmConfig {
+  /// The package release number.
+  #[serde(default = "default_rpm_release")]
+  pub release: String,
+  /// The package license, e.g. `MIT`. Defaults to `Unspecified`.
+  pub license: Option<String>,
+  /// The list of RPM dependencies your application relies on.
+  pub depends: Option<Vec<String>>,
+  /// Path to a shell script to run after the package is installed.
+  #[serde(alias = "post-install-script")]
+  pub post_install_script: Option<PathBuf>,
+  /// Path to a shell script to run after the package is removed.
+  #[serde(alias = "post-remove-script")]
+  pub post_remove_script: Option<PathBuf>,
+}
+
+impl Default for RpmConfig {
+  fn default() -> Self {
+    Self {
+      release: default_rpm_release(),
+      license: None,
+      depends: None,
+      post_install_script: None,
+      post_remove_script: None,
+    }
+  }
+}
+
+fn default_rpm_release() -> String {
+  "1".into()
+}
+
+/// Configuration for Flatpak bundles.
+///
+/// The application ID is derived from the bundle identifier.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FlatpakConfig {
+  /// The runtime the application runs on. Defaults to `org.gnome.Platform`.
+  pub runtime: Option<String>,
+  /// The runtime version. Defaults to `43`.
+  #[serde(alias = "runtime-version")]
+  pub runtime_version: Option<String>,
+  /// The SDK used to build the application. Defaults to `org.gnome.Sdk`.
+  pub sdk: Option<String>,
+  /// The sandbox permissions granted to the application, e.g. `--share=network`.
+  /// Defaults to IPC, X11, Wayland, GPU and network access.
+  /// See <https://docs.flatpak.org/en/latest/sandbox-permissions.html>.
+  #[serde(alias = "finish-args")]
+  pub finish_args: Option<Vec<String>>,
+  /// The license of the project in SPDX format, written to the AppStream metainfo file.
+  pub license: Option<String>,
+  /// Whether to run `flatpak-builder` to produce a `.flatpak` bundle.
+  /// When disabled, only the manifest and its sources are generated.
+  #[serde(default, alias = "build-bundle")]
+  pub build_bundle: bool,
+}
+
+/// The confinement level of a Snap package.
+///
+/// See <https://snapcraft.io/docs/snap-confinement>.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum SnapConfinement {
+  /// The app runs in complete isolation and can only access resources through its plugs.
+  Strict,
+  /// The app has the same access to the system as traditionally packaged apps.
+  /// Requires a manual review to be published on the Snap Store.
+  Classic,
+  /// Like strict, but permission violations are only logged. Cannot be published on the stable channel.
+  Devmode,
+}
+
+impl Default for SnapConfinement {
+  fn default() -> Self {
+    Self::Strict
+  }
+}
+
+/// Configuration for Snap packages.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct SnapConfig {
+  /// The confinement level of the package.
+  #[serde(default)]
+  pub confinement: SnapConfinement,
+  /// The interfaces the app connects to. Defaults to `network`, `home`, `desktop`, `desktop-legacy`, `wayland`, `x11` and `opengl`.
+  /// See <https://snapcraft.io/docs/supported-interfaces>.
+  pub plugs: Option<Vec<String>>,
+  /// The base snap providing the run-time environment. Defaults to `core22`.
+  pub base: Option<String>,
+  /// The list of Ubuntu packages staged in the snap, e.g. the WebKitGTK libraries.
+  /// Defaults to `libwebkit2gtk-4.0-37`.
+  #[serde(alias = "stage-packages")]
+  pub stage_packages: Option<Vec<String>>,
+  /// Whether to run `snapcraft` to produce the `.snap` package.
+  /// When disabled, only the `snapcraft.yaml` and its sources are generated.
+  #[serde(default, alias = "build-snap")]
+  pub build_snap: bool,
 }
 
 fn de_minimum_system_version<'de, D>(deserializer: D) -> Result<Option<String>, D::Error>
@@ -333,6 +486,73 @@ fn minimum_system_version() -> Option<String> {
   Some("10.13".into())
 }
 
+/// Position coordinates struct.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Position {
+  /// X coordinate.
+  pub x: u32,
+  /// Y coordinate.
+  pub y: u32,
+}
+
+/// Size of the window.
+#[derive(Default, Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct Size {
+  /// Width of the window.
+  pub width: u32,
+  /// Height of the window.
+  pub height: u32,
+}
+
+/// Configuration for Apple Disk Image (.dmg) bundles.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DmgConfig {
+  /// The name of the mounted volume. Defaults to the main binary name.
+  #[serde(alias = "volume-name")]
+  pub volume_name: Option<String>,
+  /// Path to the image to use as the background of the DMG window.
+  pub background: Option<PathBuf>,
+  /// Position of the DMG window on the screen.
+  #[serde(alias = "window-position")]
+  pub window_position: Option<Position>,
+  /// Size of the DMG window. Defaults to 660x400.
+  #[serde(alias = "window-size")]
+  pub window_size: Option<Size>,
+  /// Position of the app icon in the DMG window. Defaults to (180, 170).
+  #[serde(alias = "app-position")]
+  pub app_position: Option<Position>,
+  /// Position of the `/Applications` folder symlink in the DMG window. Defaults to (480, 170).
+  #[serde(alias = "application-folder-position")]
+  pub application_folder_position: Option<Position>,
+}
+
+/// Configuration for macOS installer packages (.pkg).
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct PkgConfig {
+  /// The directory where the app is installed. Defaults to `/Applications`.
+  #[serde(alias = "install-location")]
+  pub install_location: Option<String>,
+  /// Path to a script to run before the app is installed.
+  #[serde(alias = "pre-install-script")]
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to a script to run after the app is installed.
+  #[serde(alias = "post-install-script")]
+  pub post_install_script: Option<PathBuf>,
+  /// The "Developer ID Installer" identity used to sign the package.
+  #[serde(alias = "signing-identity")]
+  pub signing_identity: Option<String>,
+}
+
 /// Configuration for a target language for the WiX build.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -414,6 +634,85 @@ pub struct WixConfig {
   /// The required dimensions are 493px × 312px.
   #[serde(alias = "dialog-image-path")]
   pub dialog_image_path: Option<PathBuf>,
+  /// A GUID upgrade code for the MSI installer.
+  ///
+  /// It is used by Windows to detect previous installations of the app and replace them on upgrades,
+  /// so it must stay the same across all of your releases. By default it is generated from the main binary name,
+  /// so set this value if you rename the binary but want newer installers to keep upgrading existing installations.
+  #[serde(alias = "upgrade-code")]
+  pub upgrade_code: Option<String>,
+  /// Embeds the Visual C++ redistributable in the installer and installs it when the target machine lacks the runtime.
+  ///
+  /// Useful when your app or one of its sidecars is not statically linked to the C runtime.
+  /// Increases the installer size by around 14MB (x64) or 13MB (x86).
+  #[serde(default, alias = "bundle-vc-redist")]
+  pub bundle_vc_redist: bool,
+  /// The name of the Start Menu and desktop shortcuts. Defaults to the product name.
+  #[serde(alias = "shortcut-name")]
+  pub shortcut_name: Option<String>,
+  /// The name of the Start Menu folder containing the app shortcut. Defaults to the product name.
+  #[serde(alias = "start-menu-folder")]
+  pub start_menu_folder: Option<String>,
+  /// Skips the creation of the desktop shortcut.
+  #[serde(default, alias = "skip-desktop-shortcut")]
+  pub skip_desktop_shortcut: bool,
+  /// The default installation directory, using the Windows Installer folder properties,
+  /// e.g. `[ProgramFiles64Folder]Vendor\App`.
+  ///
+  /// Defaults to a folder named after the product in the Program Files folder,
+  /// or in the local programs folder for per-user installations.
+  #[serde(alias = "install-dir")]
+  pub install_dir: Option<String>,
+}
+
+/// Configuration for the NSIS installer bundle.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct NsisConfig {
+  /// A custom .nsi template to use.
+  pub template: Option<PathBuf>,
+  /// The path to the license file to render on the installer.
+  pub license: Option<PathBuf>,
+  /// The path to a bitmap file to display on the header of installers pages.
+  ///
+  /// The recommended dimensions are 150px x 57px.
+  #[serde(alias = "header-image")]
+  pub header_image: Option<PathBuf>,
+  /// The path to a bitmap file for the Welcome page and the Finish page.
+  ///
+  /// The recommended dimensions are 164px x 314px.
+  #[serde(alias = "sidebar-image")]
+  pub sidebar_image: Option<PathBuf>,
+  /// The path to an icon file used as the installer icon.
+  #[serde(alias = "installer-icon")]
+  pub installer_icon: Option<PathBuf>,
+}
+
+/// Configuration for the MSIX package bundle.
+///
+/// The package logos are generated from the largest PNG file in [`BundleConfig::icon`].
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct MsixConfig {
+  /// The package identity name. Defaults to the bundle identifier.
+  #[serde(alias = "identity-name")]
+  pub identity_name: Option<String>,
+  /// The package publisher, e.g. `CN=Contoso Software, O=Contoso Corporation, C=US`.
+  ///
+  /// Must match the subject of the signing certificate. Defaults to `CN=<publisher>`.
+  pub publisher: Option<String>,
+  /// The publisher name displayed to users. Defaults to the bundle publisher.
+  #[serde(alias = "publisher-display-name")]
+  pub publisher_display_name: Option<String>,
+  /// Additional capabilities to declare, e.g. `internetClient`. The `runFullTrust` capability is always declared.
+  #[serde(default)]
+  pub capabilities: Vec<String>,
+  /// A custom AppxManifest.xml template to use.
+  pub template: Option<PathBuf>,
 }
 
 /// Install modes for the Webview2 runtime.
@@ -473,6 +772,25 @@ impl Default for WebviewInstallMode {
   }
 }
 
+/// The scope of the Windows installation.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase")]
+pub enum WindowsInstallScope {
+  /// Installs the app for the current user only, in the user's local app data folder.
+  /// Does not require elevated privileges.
+  User,
+  /// Installs the app for all users of the machine, in the Program Files folder.
+  /// Requires elevated privileges.
+  Machine,
+}
+
+impl Default for WindowsInstallScope {
+  fn default() -> Self {
+    Self::Machine
+  }
+}
+
 /// Windows bundler configuration.
 #[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
 #[cfg_attr(feature = "schema", derive(JsonSchema))]
@@ -485,6 +803,12 @@ pub struct WindowsConfig {
   /// Specifies the SHA1 hash of the signing certificate.
   #[serde(alias = "certificate-thumbprint")]
   pub certificate_thumbprint: Option<String>,
+  /// Path to a PFX certificate file to sign with, as an alternative to [`Self::certificate_thumbprint`]
+  /// for certificates that are not imported into the Windows certificate store.
+  ///
+  /// The certificate password is read from the `TAURI_WINDOWS_CERTIFICATE_PASSWORD` environment variable.
+  #[serde(alias = "certificate-path")]
+  pub certificate_path: Option<PathBuf>,
   /// Server to use during timestamping.
   #[serde(alias = "timestamp-url")]
   pub timestamp_url: Option<String>,
@@ -510,8 +834,18 @@ pub struct WindowsConfig {
   /// The default value of this flag is `true`.
   #[serde(default = "default_allow_downgrades", alias = "allow-downgrades")]
   pub allow_downgrades: bool,
+  /// Whether the installer installs the app for the current user only or for all users of the machine.
+  ///
+  /// Per-user installations do not trigger the UAC prompt and are installed in the user's local app data folder.
+  /// Defaults to `machine`.
+  #[serde(default, alias = "install-scope")]
+  pub install_scope: WindowsInstallScope,
   /// Configuration for the MSI generated with WiX.
   pub wix: Option<WixConfig>,
+  /// Configuration for the installer generated with NSIS.
+  pub nsis: Option<NsisConfig>,
+  /// Configuration for the MSIX package.
+  pub msix: Option<MsixConfig>,
 }
 
 impl Default for WindowsConfig {
@@ -519,12 +853,16 @@ impl Default for WindowsConfig {
     Self {
       digest_algorithm: None,
       certificate_thumbprint: None,
+      certificate_path: None,
       timestamp_url: None,
       tsp: false,
       webview_install_mode: Default::default(),
       webview_fixed_runtime_path: None,
       allow_downgrades: default_allow_downgrades(),
+      install_scope: Default::default(),
       wix: None,
+      nsis: None,
+      msix: None,
     }
   }
 }
@@ -533,6 +871,110 @@ fn default_allow_downgrades() -> bool {
   true
 }
 
+/// Configuration for the checksum files generated after bundling.
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ChecksumsConfig {
+  /// Whether to write a `SHA256SUMS` file listing the SHA256 hash of every produced bundle, updater signature, delta patch and updater manifest to the bundle directory.
+  #[serde(default = "default_sha256_sums", alias = "sha256-sums")]
+  pub sha256_sums: bool,
+  /// Whether to also write a `<bundle>.sha256` file next to each hashed file.
+  #[serde(default)]
+  pub individual: bool,
+}
+
+impl Default for ChecksumsConfig {
+  fn default() -> Self {
+    Self {
+      sha256_sums: default_sha256_sums(),
+      individual: false,
+    }
+  }
+}
+
+fn default_sha256_sums() -> bool {
+  true
+}
+
+/// Configuration for the templates used to generate the installers.
+///
+/// The built-in templates can also be replaced individually by a file with the same name in the `templates` folder
+/// of the Tauri project, e.g. `templates/main.wxs`.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct TemplateConfig {
+  /// Variables added to the data of the WiX, NSIS, MSIX and AppImage templates, so custom templates can be parameterized.
+  ///
+  /// The variables don't override the values set by the bundler.
+  #[serde(default)]
+  pub vars: HashMap<String, JsonValue>,
+}
+
+/// Configuration to install the application as a background service.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct ServiceConfig {
+  /// The service name. Defaults to the main binary name.
+  pub name: Option<String>,
+  /// The service description. Defaults to the bundle short description.
+  pub description: Option<String>,
+  /// The arguments passed to the main binary when the service starts.
+  #[serde(default)]
+  pub args: Vec<String>,
+  /// Installs a systemd user unit, enabled for every user, instead of a system unit on Linux.
+  #[serde(default, alias = "user-unit")]
+  pub user_unit: bool,
+}
+
+/// Configuration for the GPG signing of the Linux packages.
+#[skip_serializing_none]
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct GpgConfig {
+  /// The id of the GPG key used to sign the deb, RPM and AppImage bundles.
+  /// Can also be set with the `TAURI_GPG_KEY_ID` environment variable.
+  ///
+  /// The key passphrase is read from the `TAURI_GPG_PASSPHRASE` environment variable.
+  #[serde(alias = "key-id")]
+  pub key_id: Option<String>,
+}
+
+/// Configuration for the custom URL schemes (deep links) that launch the application.
+#[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct DeepLinkConfig {
+  /// The URL schemes to register, without the `://` suffix, e.g. `myapp`.
+  #[serde(default)]
+  pub schemes: Vec<String>,
+}
+
+/// A file type the application is registered to open.
+#[skip_serializing_none]
+#[derive(Debug, PartialEq, Eq, Clone, Deserialize, Serialize)]
+#[cfg_attr(feature = "schema", derive(JsonSchema))]
+#[serde(rename_all = "camelCase", deny_unknown_fields)]
+pub struct FileAssociation {
+  /// The file extensions, without the leading dot.
+  pub ext: Vec<String>,
+  /// The name of the file type. Defaults to the first extension.
+  pub name: Option<String>,
+  /// The description of the file type, displayed by the file managers.
+  pub description: Option<String>,
+  /// The MIME type of the files.
+  /// Defaults to `application/x-<name>` on Linux, where it is required to register the association.
+  #[serde(alias = "mime-type")]
+  pub mime_type: Option<String>,
+  /// The icon of the files. Must be a `.ico` file on Windows and a `.icns` file on macOS.
+  /// Defaults to the app icon.
+  pub icon: Option<PathBuf>,
+}
+
 /// Configuration for tauri-bundler.
 #[skip_serializing_none]
 #[derive(Debug, Default, PartialEq, Eq, Clone, Deserialize, Serialize)]
@@ -542,7 +984,7 @@ pub struct BundleConfig {
   /// Whether Tauri should bundle your application or just output the executable.
   #[serde(default)]
   pub active: bool,
-  /// The bundle targets, currently supports ["deb", "appimage", "msi", "app", "dmg", "updater"] or "all".
+  /// The bundle targets, currently supports ["deb", "rpm", "flatpak", "snap", "appimage", "msi", "nsis", "msix", "portable", "app", "dmg", "pkg", "updater"] or "all".
   #[serde(default)]
   pub targets: BundleTarget,
   /// The application identifier in reverse domain name notation (e.g. `com.tauri.example`).
@@ -561,6 +1003,12 @@ pub struct BundleConfig {
   /// Each resource is a path to a file or directory.
   /// Glob patterns are supported.
   pub resources: Option<Vec<String>>,
+  /// Glob patterns of the resource files to leave out of the bundles.
+  ///
+  /// The patterns are matched against the resource paths, including the files found in resource directories,
+  /// e.g. `assets/**/*.map`.
+  #[serde(alias = "exclude-resources")]
+  pub exclude_resources: Option<Vec<String>>,
   /// A copyright string associated with your application.
   pub copyright: Option<String>,
   /// The application kind.
@@ -580,6 +1028,21 @@ pub struct BundleConfig {
   /// Configuration for the Debian bundle.
   #[serde(default)]
   pub deb: DebConfig,
+  /// Configuration for the RPM bundle.
+  #[serde(default)]
+  pub rpm: RpmConfig,
+  /// Configuration for the Flatpak bundle.
+  #[serde(default)]
+  pub flatpak: FlatpakConfig,
+  /// Configuration for the Snap package.
+  #[serde(default)]
+  pub snap: SnapConfig,
+  /// Configuration for the DMG bundle.
+  #[serde(default)]
+  pub dmg: DmgConfig,
+  /// Configuration for the macOS installer package.
+  #[serde(default)]
+  pub pkg: PkgConfig,
   /// Configuration for the macOS bundles.
   #[serde(rename = "macOS", default)]
   pub macos: MacConfig,
@@ -596,9 +1059,34 @@ pub struct BundleConfig {
   /// so don't forget to provide binaries for all targeted platforms.
   #[serde(alias = "external-bin")]
   pub external_bin: Option<Vec<String>>,
+  /// The file types the application is registered to open.
+  ///
+  /// Registered in the MSI installer, the macOS `Info.plist` and the Linux desktop entry.
+  #[serde(alias = "file-associations")]
+  pub file_associations: Option<Vec<FileAssociation>>,
+  /// The custom URL schemes that launch the application.
+  ///
+  /// Registered in the MSI installer, the macOS `Info.plist` and the Linux desktop entry.
+  #[serde(default, alias = "deep-link")]
+  pub deep_link: DeepLinkConfig,
+  /// Installs the main binary as a service, started on boot and when the package is installed.
+  ///
+  /// On Windows, the MSI installer registers a Windows service, so the binary must implement the service control protocol
+  /// and the install scope must be `machine`.
+  /// On Linux, the deb and RPM packages ship a systemd unit and enable it on install.
+  pub service: Option<ServiceConfig>,
+  /// Configuration for the GPG signing of the Linux bundles.
+  #[serde(default)]
+  pub gpg: GpgConfig,
   /// Configuration for the Windows bundle.
   #[serde(default)]
   pub windows: WindowsConfig,
+  /// Configuration for the checksum files generated for the bundles.
+  #[serde(default)]
+  pub checksums: ChecksumsConfig,
+  /// Configuration for the installer templates.
+  #[serde(default)]
+  pub template: TemplateConfig,
 }
 
 /// A CLI argument definition.
@@ -2462,6 +2950,10 @@ pub struct SystemTrayConfig {
   pub menu_on_left_click: bool,
   /// Title for MacOS tray
   pub title: Option<String>,
+  /// The tooltip shown when hovering the tray icon.
+  ///
+  /// Not supported on Linux.
+  pub tooltip: Option<String>,
 }
 
 fn default_tray_menu_on_left_click() -> bool {
@@ -2576,6 +3068,15 @@ pub struct BuildConfig {
   /// The TAURI_PLATFORM, TAURI_ARCH, TAURI_FAMILY, TAURI_PLATFORM_VERSION, TAURI_PLATFORM_TYPE and TAURI_DEBUG environment variables are set if you perform conditional compilation.
   #[serde(alias = "before-bundle-command")]
   pub before_bundle_command: Option<HookCommand>,
+  /// A shell command to run after the bundling phase in `tauri build`, once the updater bundles are signed.
+  ///
+  /// The `TAURI_BUNDLE_ARTIFACTS` environment variable is set to a JSON array describing the produced bundles, updater signatures, patches, manifest and checksum files,
+  /// with their `path`, `format`, `arch`, `sha256` and `size`, and `TAURI_BUNDLE_CONFIG` is set to the resolved `tauri > bundle` configuration as JSON,
+  /// in addition to the environment variables set for `beforeBundleCommand`.
+  ///
+  /// This is a single hook for every bundle format: use the `format` of each artifact to handle the formats differently.
+  #[serde(alias = "after-bundle-command")]
+  pub after_bundle_command: Option<HookCommand>,
   /// Features passed to `cargo` commands.
   pub features: Option<Vec<String>>,
   /// Whether we should inject the Tauri API on `window.__TAURI__` or not.
@@ -2592,6 +3093,7 @@ impl Default for BuildConfig {
       before_dev_command: None,
       before_build_command: None,
       before_bundle_command: None,
+      after_bundle_command: None,
       features: None,
       with_global_tauri: false,
     }
@@ -2806,6 +3308,7 @@ fn default_build() -> BuildConfig {
     before_dev_command: None,
     before_build_command: None,
     before_bundle_command: None,
+    after_bundle_command: None,
     features: None,
     with_global_tauri: false,
   }
@@ -3243,15 +3746,27 @@ mod build {
       let active = self.active;
       let targets = quote!(Default::default());
       let resources = quote!(None);
+      let exclude_resources = quote!(None);
       let copyright = quote!(None);
       let category = quote!(None);
       let short_description = quote!(None);
       let long_description = quote!(None);
       let appimage = quote!(Default::default());
       let deb = quote!(Default::default());
+      let rpm = quote!(Default::default());
+      let flatpak = quote!(Default::default());
+      let snap = quote!(Default::default());
+      let dmg = quote!(Default::default());
+      let pkg = quote!(Default::default());
       let macos = quote!(Default::default());
       let external_bin = opt_vec_str_lit(self.external_bin.as_ref());
+      let file_associations = quote!(None);
+      let deep_link = quote!(Default::default());
+      let service = quote!(None);
+      let gpg = quote!(Default::default());
       let windows = &self.windows;
+      let checksums = quote!(Default::default());
+      let template = quote!(Default::default());
 
       literal_struct!(
         tokens,
@@ -3262,15 +3777,27 @@ mod build {
         icon,
         targets,
         resources,
+        exclude_resources,
         copyright,
         category,
         short_description,
         long_description,
         appimage,
         deb,
+        rpm,
+        flatpak,
+        snap,
+        dmg,
+        pkg,
         macos,
         external_bin,
-        windows
+        file_associations,
+        deep_link,
+        service,
+        gpg,
+        windows,
+        checksums,
+        template
       );
     }
   }
@@ -3300,6 +3827,7 @@ mod build {
       let before_dev_command = quote!(None);
       let before_build_command = quote!(None);
       let before_bundle_command = quote!(None);
+      let after_bundle_command = quote!(None);
       let features = quote!(None);
 
       literal_struct!(
@@ -3312,6 +3840,7 @@ mod build {
         before_dev_command,
         before_build_command,
         before_bundle_command,
+        after_bundle_command,
         features
       );
     }
@@ -3446,13 +3975,15 @@ mod build {
       let menu_on_left_click = self.menu_on_left_click;
       let icon_path = path_buf_lit(&self.icon_path);
       let title = opt_str_lit(self.title.as_ref());
+      let tooltip = opt_str_lit(self.tooltip.as_ref());
       literal_struct!(
         tokens,
         SystemTrayConfig,
         icon_path,
         icon_as_template,
         menu_on_left_click,
-        title
+        title,
+        tooltip
       );
     }
   }
@@ -3677,15 +4208,27 @@ mod test {
         publisher: None,
         icon: Vec::new(),
         resources: None,
+        exclude_resources: None,
         copyright: None,
         category: None,
         short_description: None,
         long_description: None,
         appimage: Default::default(),
         deb: Default::default(),
+        rpm: Default::default(),
+        flatpak: Default::default(),
+        snap: Default::default(),
+        dmg: Default::default(),
+        pkg: Default::default(),
         macos: Default::default(),
         external_bin: None,
+        file_associations: None,
+        deep_link: Default::default(),
+        service: None,
+        gpg: Default::default(),
         windows: Default::default(),
+        checksums: Default::default(),
+        template: Default::default(),
       },
       cli: None,
       updater: UpdaterConfig {
@@ -3716,6 +4259,7 @@ mod test {
       before_dev_command: None,
       before_build_command: None,
       before_bundle_command: None,
+      after_bundle_command: None,
       features: None,
       with_global_tauri: false,
     };
@@ -130,6 +130,16 @@ pub enum ConfigError {
     feature: String,
   },
 
+  /// Failed to substitute a `${...}` variable in a configuration value.
+  #[error("unable to substitute `${{{variable}}}` in the Tauri config because {reason}")]
+  Substitution {
+    /// The variable that couldn't be substituted.
+    variable: String,
+
+    /// Why the substitution failed.
+    reason: String,
+  },
+
   /// A generic IO error with context of what caused it.
   #[error("unable to read Tauri config file at {path} because {error}")]
   Io {
@@ -199,6 +209,165 @@ pub fn read_platform(root_dir: PathBuf) -> Result<Option<(Value, PathBuf)>, Conf
   }
 }
 
+/// The `build` keys holding shell commands, where `${...}` is left to the shell.
+const SHELL_COMMAND_KEYS: &[&str] = &[
+  "beforeDevCommand",
+  "before-dev-command",
+  "beforeBuildCommand",
+  "before-build-command",
+  "beforeBundleCommand",
+  "before-bundle-command",
+  "afterBundleCommand",
+  "after-bundle-command",
+];
+
+/// The variables resolved by [`substitute_variables`].
+#[derive(Debug, Default)]
+pub struct SubstitutedVariables {
+  /// The names of the environment variables that were substituted.
+  pub env: Vec<String>,
+  /// Whether `${gitHash}` was substituted.
+  pub git_hash: bool,
+}
+
+/// Substitutes the `${...}` variables in the string values of the configuration.
+///
+/// - `${version}` is replaced with the given package version;
+/// - `${gitHash}` is replaced with the short hash of the current git commit;
+/// - `${NAME}` is replaced with the value of the `NAME` environment variable, which must be set.
+///
+/// Use `$${` to write a literal `${`.
+/// The `build` hook commands are not substituted, since `${...}` is a shell expansion there.
+///
+/// Returns the substituted variables, so build scripts can rerun when they change.
+pub fn substitute_variables(
+  config: &mut Value,
+  package_version: Option<&str>,
+) -> Result<SubstitutedVariables, ConfigError> {
+  substitute_variables_with(config, package_version, |name| std::env::var(name))
+}
+
+/// [`substitute_variables`] with the given environment variable lookup.
+fn substitute_variables_with<E: Fn(&str) -> Result<String, std::env::VarError>>(
+  config: &mut Value,
+  package_version: Option<&str>,
+  env: E,
+) -> Result<SubstitutedVariables, ConfigError> {
+  let mut git_hash = None;
+  let mut substituted = SubstitutedVariables::default();
+  let mut resolve = |variable: &str| match variable {
+    "version" => {
+      package_version
+        .map(ToString::to_string)
+        .ok_or_else(|| ConfigError::Substitution {
+          variable: variable.into(),
+          reason: "the package version is not available".into(),
+        })
+    }
+    "gitHash" => {
+      if git_hash.is_none() {
+        git_hash.replace(
+          current_git_hash().map_err(|reason| ConfigError::Substitution {
+            variable: variable.into(),
+            reason,
+          })?,
+        );
+      }
+      substituted.git_hash = true;
+      Ok(git_hash.clone().unwrap())
+    }
+    name => {
+      let value = env(name).map_err(|e| ConfigError::Substitution {
+        variable: variable.into(),
+        reason: e.to_string(),
+      })?;
+      if !substituted.env.iter().any(|env| env == name) {
+        substituted.env.push(name.into());
+      }
+      Ok(value)
+    }
+  };
+
+  if let Value::Object(root) = config {
+    for (key, value) in root.iter_mut() {
+      match value {
+        Value::Object(build) if key == "build" => {
+          for (key, value) in build.iter_mut() {
+            if !SHELL_COMMAND_KEYS.contains(&key.as_str()) {
+              substitute_value(value, &mut resolve)?;
+            }
+          }
+        }
+        value => substitute_value(value, &mut resolve)?,
+      }
+    }
+  } else {
+    substitute_value(config, &mut resolve)?;
+  }
+
+  Ok(substituted)
+}
+
+fn substitute_value<F: FnMut(&str) -> Result<String, ConfigError>>(
+  value: &mut Value,
+  resolve: &mut F,
+) -> Result<(), ConfigError> {
+  match value {
+    Value::String(s) if s.contains('$') => *s = substitute_str(s, resolve)?,
+    Value::Array(values) => {
+      for value in values {
+        substitute_value(value, resolve)?;
+      }
+    }
+    Value::Object(map) => {
+      for value in map.values_mut() {
+        substitute_value(value, resolve)?;
+      }
+    }
+    _ => {}
+  }
+  Ok(())
+}
+
+fn substitute_str<F: FnMut(&str) -> Result<String, ConfigError>>(
+  s: &str,
+  resolve: &mut F,
+) -> Result<String, ConfigError> {
+  let mut substituted = String::with_capacity(s.len());
+  let mut rest = s;
+  while let Some(index) = rest.find('$') {
+    substituted.push_str(&rest[..index]);
+    rest = &rest[index..];
+    if let Some(after) = rest.strip_prefix("$${") {
+      substituted.push_str("${");
+      rest = after;
+    } else if let Some((variable, after)) = rest
+      .strip_prefix("${")
+      .and_then(|after| after.split_once('}'))
+    {
+      substituted.push_str(&resolve(variable)?);
+      rest = after;
+    } else {
+      substituted.push('$');
+      rest = &rest[1..];
+    }
+  }
+  substituted.push_str(rest);
+  Ok(substituted)
+}
+
+fn current_git_hash() -> Result<String, String> {
+  let output = std::process::Command::new("git")
+    .args(["rev-parse", "--short", "HEAD"])
+    .output()
+    .map_err(|e| format!("failed to run git: {}", e))?;
+  if output.status.success() {
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+  } else {
+    Err(String::from_utf8_lossy(&output.stderr).trim().to_string())
+  }
+}
+
 /// Check if a supported config file exists at path.
 ///
 /// The passed path is expected to be the path to the "default" configuration format, in this case
@@ -386,3 +555,48 @@ fn read_to_string(path: &Path) -> Result<String, ConfigError> {
     error,
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::substitute_variables_with;
+  use std::env::VarError;
+
+  #[test]
+  fn substitutes_variables() {
+    let env = |name: &str| match name {
+      "TAURI_TEST_SUBSTITUTION" => Ok("com.tauri.test".to_string()),
+      _ => Err(VarError::NotPresent),
+    };
+    let mut config = serde_json::json!({
+      "build": {
+        "distDir": "../dist-${TAURI_TEST_SUBSTITUTION}",
+        "beforeBuildCommand": "echo ${HOME}"
+      },
+      "package": { "version": "${version}" },
+      "tauri": {
+        "bundle": { "identifier": "${TAURI_TEST_SUBSTITUTION}" },
+        "windows": [{ "title": "$${version} costs $5" }]
+      }
+    });
+    let substituted = substitute_variables_with(&mut config, Some("1.2.3"), env).unwrap();
+    assert_eq!(
+      config,
+      serde_json::json!({
+        "build": {
+          "distDir": "../dist-com.tauri.test",
+          "beforeBuildCommand": "echo ${HOME}"
+        },
+        "package": { "version": "1.2.3" },
+        "tauri": {
+          "bundle": { "identifier": "com.tauri.test" },
+          "windows": [{ "title": "${version} costs $5" }]
+        }
+      })
+    );
+    assert_eq!(substituted.env, vec!["TAURI_TEST_SUBSTITUTION".to_string()]);
+    assert!(!substituted.git_hash);
+
+    let mut config = serde_json::json!({ "package": { "version": "${TAURI_TEST_UNSET}" } });
+    assert!(substitute_variables_with(&mut config, None, env).is_err());
+  }
+}
@@ -168,6 +168,12 @@ pub struct Env {
   /// The APPDIR environment variable.
   #[cfg(target_os = "linux")]
   pub appdir: Option<std::ffi::OsString>,
+  /// The FLATPAK_ID environment variable, set when running inside a Flatpak sandbox.
+  #[cfg(target_os = "linux")]
+  pub flatpak_id: Option<std::ffi::OsString>,
+  /// The SNAP environment variable, set to the mounted location of the snap when running inside it.
+  #[cfg(target_os = "linux")]
+  pub snap: Option<std::ffi::OsString>,
   /// The command line arguments of the current process.
   pub args: Vec<String>,
 }
@@ -183,6 +189,10 @@ impl Default for Env {
         appimage: std::env::var_os("APPIMAGE"),
         #[cfg(target_os = "linux")]
         appdir: std::env::var_os("APPDIR"),
+        #[cfg(target_os = "linux")]
+        flatpak_id: std::env::var_os("FLATPAK_ID"),
+        #[cfg(target_os = "linux")]
+        snap: std::env::var_os("SNAP"),
         args,
       };
       if env.appimage.is_some() || env.appdir.is_some() {
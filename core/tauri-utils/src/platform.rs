@@ -145,7 +145,10 @@ pub fn target_triple() -> crate::Result<String> {
 ///
 /// On Linux, when running in an AppImage the `APPDIR` variable will be set to
 /// the mounted location of the app, and the resource dir will be
-/// `${APPDIR}/usr/lib/${exe_name}`. If not running in an AppImage, the path is
+/// `${APPDIR}/usr/lib/${exe_name}`. When running in a Flatpak sandbox the `FLATPAK_ID`
+/// variable is set and the path is `/app/lib/${exe_name}`. When running in a snap the `SNAP`
+/// variable is set to the mounted location of the snap and the path is
+/// `${SNAP}/usr/lib/${exe_name}`. Otherwise the path is
 /// `/usr/lib/${exe_name}`.  When running the app from
 /// `src-tauri/target/(debug|release)/`, the path is
 /// `${exe_dir}/../lib/${exe_name}`.
@@ -170,26 +173,7 @@ pub fn resource_dir(package_info: &PackageInfo, env: &Env) -> crate::Result<Path
 
   #[cfg(target_os = "linux")]
   {
-    res = if curr_dir.ends_with("/data/usr/bin") {
-      // running from the deb bundle dir
-      exe_dir
-        .join(format!("../lib/{}", package_info.package_name()))
-        .canonicalize()
-        .map_err(Into::into)
-    } else if let Some(appdir) = &env.appdir {
-      let appdir: &std::path::Path = appdir.as_ref();
-      Ok(PathBuf::from(format!(
-        "{}/usr/lib/{}",
-        appdir.display(),
-        package_info.package_name()
-      )))
-    } else {
-      // running bundle
-      Ok(PathBuf::from(format!(
-        "/usr/lib/{}",
-        package_info.package_name()
-      )))
-    };
+    res = linux_resource_dir(exe_dir, &package_info.package_name(), env);
   }
 
   #[cfg(target_os = "macos")]
@@ -203,6 +187,41 @@ pub fn resource_dir(package_info: &PackageInfo, env: &Env) -> crate::Result<Path
   res
 }
 
+#[cfg(target_os = "linux")]
+fn linux_resource_dir(
+  exe_dir: &std::path::Path,
+  package_name: &str,
+  env: &Env,
+) -> crate::Result<PathBuf> {
+  if exe_dir.display().to_string().ends_with("/data/usr/bin") {
+    // running from the deb bundle dir
+    exe_dir
+      .join(format!("../lib/{}", package_name))
+      .canonicalize()
+      .map_err(Into::into)
+  } else if let Some(appdir) = &env.appdir {
+    let appdir: &std::path::Path = appdir.as_ref();
+    Ok(PathBuf::from(format!(
+      "{}/usr/lib/{}",
+      appdir.display(),
+      package_name
+    )))
+  } else if let Some(snap) = &env.snap {
+    let snap: &std::path::Path = snap.as_ref();
+    Ok(PathBuf::from(format!(
+      "{}/usr/lib/{}",
+      snap.display(),
+      package_name
+    )))
+  } else if env.flatpak_id.is_some() {
+    // running inside a Flatpak sandbox, where the app is installed to `/app`
+    Ok(PathBuf::from(format!("/app/lib/{}", package_name)))
+  } else {
+    // running bundle
+    Ok(PathBuf::from(format!("/usr/lib/{}", package_name)))
+  }
+}
+
 #[cfg(windows)]
 pub use windows_platform::{is_windows_7, windows_version};
 
@@ -286,3 +305,64 @@ mod windows_platform {
     }
   }
 }
+
+#[cfg(all(test, target_os = "linux"))]
+mod tests {
+  use super::linux_resource_dir;
+  use crate::Env;
+  use std::path::{Path, PathBuf};
+
+  fn env() -> Env {
+    Env {
+      appimage: None,
+      appdir: None,
+      flatpak_id: None,
+      snap: None,
+      args: Vec::new(),
+    }
+  }
+
+  #[test]
+  fn resource_dir_of_installed_bundle() {
+    assert_eq!(
+      linux_resource_dir(Path::new("/usr/bin"), "tauri-app", &env()).unwrap(),
+      PathBuf::from("/usr/lib/tauri-app")
+    );
+  }
+
+  #[test]
+  fn resource_dir_of_appimage() {
+    let env = Env {
+      appdir: Some("/tmp/.mount_tauri".into()),
+      ..env()
+    };
+    assert_eq!(
+      linux_resource_dir(Path::new("/tmp/.mount_tauri/usr/bin"), "tauri-app", &env).unwrap(),
+      PathBuf::from("/tmp/.mount_tauri/usr/lib/tauri-app")
+    );
+  }
+
+  #[test]
+  fn resource_dir_of_flatpak() {
+    let env = Env {
+      flatpak_id: Some("com.tauri.app".into()),
+      ..env()
+    };
+    assert_eq!(
+      linux_resource_dir(Path::new("/app/bin"), "tauri-app", &env).unwrap(),
+      PathBuf::from("/app/lib/tauri-app")
+    );
+  }
+
+  #[test]
+  fn resource_dir_of_snap() {
+    let env = Env {
+      snap: Some("/snap/tauri-app/x1".into()),
+      ..env()
+    };
+    assert_eq!(
+      linux_resource_dir(Path::new("/snap/tauri-app/x1/usr/bin"), "tauri-app", &env).unwrap(),
+      PathBuf::from("/snap/tauri-app/x1/usr/lib/tauri-app")
+    );
+  }
+}
@@ -53,6 +53,10 @@ pub struct ResourcePaths<'a> {
   current_pattern: Option<String>,
   /// whether the current pattern is valid or not.
   current_pattern_is_valid: bool,
+  /// the glob patterns of the files to skip.
+  exclude: Vec<glob::Pattern>,
+  /// the error of an invalid exclude pattern, returned by the next iteration.
+  exclude_error: Option<glob::PatternError>,
 }
 
 impl<'a> ResourcePaths<'a> {
@@ -65,14 +69,39 @@ impl<'a> ResourcePaths<'a> {
       allow_walk,
       current_pattern: None,
       current_pattern_is_valid: false,
+      exclude: Vec::new(),
+      exclude_error: None,
     }
   }
+
+  /// Skips the files matching any of the given glob patterns.
+  ///
+  /// Files found by walking a directory are matched with the directory path as prefix.
+  pub fn exclude(mut self, patterns: &[String]) -> Self {
+    for pattern in patterns {
+      match glob::Pattern::new(pattern) {
+        Ok(pattern) => self.exclude.push(pattern),
+        Err(error) => self.exclude_error = Some(error),
+      }
+    }
+    self
+  }
+
+  fn is_excluded(&self, path: &Path) -> bool {
+    self
+      .exclude
+      .iter()
+      .any(|pattern| pattern.matches_path(path))
+  }
 }
 
 impl<'a> Iterator for ResourcePaths<'a> {
   type Item = crate::Result<PathBuf>;
 
   fn next(&mut self) -> Option<crate::Result<PathBuf>> {
+    if let Some(error) = self.exclude_error.take() {
+      return Some(Err(error.into()));
+    }
     loop {
       if let Some(ref mut walk_entries) = self.walk_iter {
         if let Some(entry) = walk_entries.next() {
@@ -85,6 +114,9 @@ impl<'a> Iterator for ResourcePaths<'a> {
             continue;
           }
           self.current_pattern_is_valid = true;
+          if self.is_excluded(path) {
+            continue;
+          }
           return Some(Ok(path.to_path_buf()));
         }
       }
@@ -97,7 +129,8 @@ impl<'a> Iterator for ResourcePaths<'a> {
           };
           if path.is_dir() {
             if self.allow_walk {
-              let walk = walkdir::WalkDir::new(path);
+              let walk =
+                walkdir::WalkDir::new(path).sort_by(|a, b| a.file_name().cmp(b.file_name()));
               self.walk_iter = Some(walk.into_iter());
               continue;
             } else {
@@ -105,6 +138,9 @@ impl<'a> Iterator for ResourcePaths<'a> {
             }
           }
           self.current_pattern_is_valid = true;
+          if self.is_excluded(&path) {
+            continue;
+          }
           return Some(Ok(path));
         } else if let Some(current_path) = &self.current_pattern {
           if !self.current_pattern_is_valid {
@@ -64,6 +64,7 @@ pub struct SystemTray {
   icon_as_template_set: bool,
   #[cfg(target_os = "macos")]
   title: Option<String>,
+  tooltip: Option<String>,
 }
 
 impl fmt::Debug for SystemTray {
@@ -71,7 +72,8 @@ impl fmt::Debug for SystemTray {
     let mut d = f.debug_struct("SystemTray");
     d.field("id", &self.id)
       .field("icon", &self.icon)
-      .field("menu", &self.menu);
+      .field("menu", &self.menu)
+      .field("tooltip", &self.tooltip);
     #[cfg(target_os = "macos")]
     {
       d.field("icon_as_template", &self.icon_as_template)
@@ -98,6 +100,7 @@ impl Default for SystemTray {
       menu_on_left_click_set: false,
       #[cfg(target_os = "macos")]
       title: None,
+      tooltip: None,
     }
   }
 }
@@ -257,6 +260,29 @@ impl SystemTray {
     self
   }
 
+  /// Sets the tray icon tooltip.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** Unsupported
+  ///
+  /// # Examples
+  ///
+  /// ```
+  /// use tauri::SystemTray;
+  ///
+  /// tauri::Builder::default()
+  ///   .setup(|app| {
+  ///     let tray_handle = SystemTray::new().with_tooltip("My App").build(app)?;
+  ///     Ok(())
+  ///   });
+  /// ```
+  #[must_use]
+  pub fn with_tooltip(mut self, tooltip: &str) -> Self {
+    self.tooltip = Some(tooltip.to_owned());
+    self
+  }
+
   /// Sets the event listener for this system tray.
   ///
   /// # Examples
@@ -380,6 +406,14 @@ impl SystemTray {
           .and_then(|t| t.title.clone())
       }
     }
+    if self.tooltip.is_none() {
+      self.tooltip = manager
+        .config()
+        .tauri
+        .system_tray
+        .as_ref()
+        .and_then(|t| t.tooltip.clone());
+    }
 
     let tray_id = self.id.clone();
 
@@ -393,6 +427,10 @@ impl SystemTray {
       runtime_tray = runtime_tray.with_menu(menu);
     }
 
+    if let Some(tooltip) = self.tooltip {
+      runtime_tray = runtime_tray.with_tooltip(&tooltip);
+    }
+
     if let Some(on_event) = self.on_event {
       let ids_ = ids.clone();
       let tray_id_ = tray_id.clone();
@@ -610,6 +648,15 @@ impl<R: Runtime> SystemTrayHandle<R> {
     self.inner.set_title(title).map_err(Into::into)
   }
 
+  /// Updates the tray icon tooltip.
+  ///
+  /// ## Platform-specific
+  ///
+  /// - **Linux:** Unsupported
+  pub fn set_tooltip(&self, tooltip: &str) -> crate::Result<()> {
+    self.inner.set_tooltip(tooltip).map_err(Into::into)
+  }
+
   /// Destroys this system tray.
   pub fn destroy(&self) -> crate::Result<()> {
     self.inner.destroy().map_err(Into::into)
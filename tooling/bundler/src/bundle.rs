@@ -4,29 +4,40 @@
 // SPDX-License-Identifier: MIT
 
 mod category;
+mod checksums;
 mod common;
 #[cfg(target_os = "linux")]
 mod linux;
 #[cfg(target_os = "macos")]
 mod macos;
+mod nsis;
 mod path_utils;
 mod platform;
 mod settings;
+mod template;
 mod updater_bundle;
 #[cfg(target_os = "windows")]
 mod windows;
+mod wix_guid;
+#[cfg(not(target_os = "windows"))]
+mod wixl;
 
 pub use self::{
   category::AppCategory,
+  checksums::write_checksums,
   settings::{
-    BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings, PackageType,
-    Settings, SettingsBuilder, UpdaterSettings,
+    BundleBinary, BundleSettings, ChecksumSettings, DebianSettings, DmgSettings, FlatpakSettings,
+    MacOsSettings, MsiBackend, PackageSettings, PackageType, PkgSettings, RpmSettings, Settings,
+    SettingsBuilder, SnapSettings, UpdaterSettings,
   },
+  template::register_template_helpers,
 };
 use log::{info, warn};
-pub use settings::{WindowsSettings, WixLanguage, WixLanguageConfig, WixSettings};
+pub use settings::{
+  MsixSettings, NsisSettings, WindowsSettings, WixLanguage, WixLanguageConfig, WixSettings,
+};
 
-use std::{fmt::Write, path::PathBuf};
+use std::{fmt::Write, path::PathBuf, sync::Arc};
 
 /// Generated bundle metadata.
 #[derive(Debug)]
@@ -39,39 +50,43 @@ pub struct Bundle {
 
 /// Bundles the project.
 /// Returns the list of paths where the bundles can be found.
+///
+/// Package types that don't depend on other bundles are built concurrently,
+/// with at most one thread per available CPU. Each thread is named after its package type.
 pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
-  let mut bundles = Vec::new();
   let package_types = settings.package_types()?;
+  let settings = Arc::new(settings);
+
+  // the Windows bundlers copy the binaries concurrently, so they are signed once beforehand
+  #[cfg(target_os = "windows")]
+  windows::sign::sign_binaries(&settings)?;
 
-  for package_type in &package_types {
-    let bundle_paths = match package_type {
-      #[cfg(target_os = "macos")]
-      PackageType::MacOsBundle => macos::app::bundle_project(&settings)?,
-      #[cfg(target_os = "macos")]
-      PackageType::IosBundle => macos::ios::bundle_project(&settings)?,
-      #[cfg(target_os = "windows")]
-      PackageType::WindowsMsi => windows::msi::bundle_project(&settings, false)?,
-      #[cfg(target_os = "linux")]
-      PackageType::Deb => linux::debian::bundle_project(&settings)?,
-      #[cfg(target_os = "linux")]
-      PackageType::Rpm => linux::rpm::bundle_project(&settings)?,
-      #[cfg(target_os = "linux")]
-      PackageType::AppImage => linux::appimage::bundle_project(&settings)?,
-      // dmg is dependant of MacOsBundle, we send our bundles to prevent rebuilding
-      #[cfg(target_os = "macos")]
-      PackageType::Dmg => macos::dmg::bundle_project(&settings, &bundles)?,
-      // updater is dependant of multiple bundle, we send our bundles to prevent rebuilding
-      PackageType::Updater => updater_bundle::bundle_project(&settings, &bundles)?,
-      _ => {
-        warn!("ignoring {:?}", package_type);
-        continue;
+  // dmg, pkg and updater depend on the other bundles, so they are built once those are done
+  let (dependent_types, independent_types): (Vec<_>, Vec<_>) =
+    package_types.into_iter().partition(is_dependent);
+
+  let mut bundles = if independent_types.len() > 1 {
+    bundle_concurrently(&settings, independent_types)?
+  } else {
+    let mut bundles = Vec::new();
+    for package_type in independent_types {
+      if let Some(bundle_paths) = bundle_package_type(&settings, &package_type, &bundles)? {
+        bundles.push(Bundle {
+          package_type,
+          bundle_paths,
+        });
       }
-    };
+    }
+    bundles
+  };
 
-    bundles.push(Bundle {
-      package_type: package_type.to_owned(),
-      bundle_paths,
-    });
+  for package_type in dependent_types {
+    if let Some(bundle_paths) = bundle_package_type(&settings, &package_type, &bundles)? {
+      bundles.push(Bundle {
+        package_type,
+        bundle_paths,
+      });
+    }
   }
 
   let pluralised = if bundles.len() == 1 {
@@ -96,6 +111,109 @@ pub fn bundle_project(settings: Settings) -> crate::Result<Vec<Bundle>> {
   Ok(bundles)
 }
 
+/// Whether the package type reuses the bundles built before it.
+fn is_dependent(package_type: &PackageType) -> bool {
+  matches!(
+    package_type,
+    PackageType::Dmg | PackageType::Pkg | PackageType::Updater
+  )
+}
+
+/// Bundles the given package types on batches of named threads.
+fn bundle_concurrently(
+  settings: &Arc<Settings>,
+  package_types: Vec<PackageType>,
+) -> crate::Result<Vec<Bundle>> {
+  let parallelism = std::thread::available_parallelism()
+    .map(|n| n.get())
+    .unwrap_or(1);
+  let mut bundles = Vec::new();
+
+  for batch in package_types.chunks(parallelism) {
+    let handles = batch
+      .iter()
+      .map(|package_type| {
+        let settings = settings.clone();
+        let package_type = *package_type;
+        std::thread::Builder::new()
+          .name(package_type.short_name().to_string())
+          .spawn(move || {
+            bundle_package_type(&settings, &package_type, &[])
+              .map(|paths| paths.map(|bundle_paths| (package_type, bundle_paths)))
+          })
+          .map_err(crate::Error::from)
+      })
+      .collect::<crate::Result<Vec<_>>>()?;
+
+    // wait for every thread of the batch before reporting the first error
+    let results = handles
+      .into_iter()
+      .map(|handle| {
+        handle
+          .join()
+          .map_err(|_| crate::Error::GenericError("bundler thread panicked".into()))?
+      })
+      .collect::<Vec<_>>();
+    for result in results {
+      if let Some((package_type, bundle_paths)) = result? {
+        bundles.push(Bundle {
+          package_type,
+          bundle_paths,
+        });
+      }
+    }
+  }
+
+  Ok(bundles)
+}
+
+/// Bundles a single package type.
+/// Returns `None` if the package type is not supported on this platform.
+fn bundle_package_type(
+  settings: &Settings,
+  package_type: &PackageType,
+  bundles: &[Bundle],
+) -> crate::Result<Option<Vec<PathBuf>>> {
+  let bundle_paths = match package_type {
+    #[cfg(target_os = "macos")]
+    PackageType::MacOsBundle => macos::app::bundle_project(settings)?,
+    #[cfg(target_os = "macos")]
+    PackageType::IosBundle => macos::ios::bundle_project(settings)?,
+    #[cfg(target_os = "windows")]
+    PackageType::WindowsMsi => windows::msi::bundle_project(settings, false)?,
+    #[cfg(not(target_os = "windows"))]
+    PackageType::WindowsMsi => wixl::bundle_project(settings)?,
+    PackageType::Nsis => nsis::bundle_project(settings)?,
+    #[cfg(target_os = "windows")]
+    PackageType::Msix => windows::msix::bundle_project(settings)?,
+    #[cfg(target_os = "windows")]
+    PackageType::Portable => windows::portable::bundle_project(settings)?,
+    #[cfg(target_os = "linux")]
+    PackageType::Deb => linux::debian::bundle_project(settings)?,
+    #[cfg(target_os = "linux")]
+    PackageType::Rpm => linux::rpm::bundle_project(settings)?,
+    #[cfg(target_os = "linux")]
+    PackageType::Flatpak => linux::flatpak::bundle_project(settings)?,
+    #[cfg(target_os = "linux")]
+    PackageType::Snap => linux::snap::bundle_project(settings)?,
+    #[cfg(target_os = "linux")]
+    PackageType::AppImage => linux::appimage::bundle_project(settings)?,
+    // dmg is dependant of MacOsBundle, we send our bundles to prevent rebuilding
+    #[cfg(target_os = "macos")]
+    PackageType::Dmg => macos::dmg::bundle_project(settings, bundles)?,
+    // pkg is dependant of MacOsBundle, we send our bundles to prevent rebuilding
+    #[cfg(target_os = "macos")]
+    PackageType::Pkg => macos::pkg::bundle_project(settings, bundles)?,
+    // updater is dependant of multiple bundle, we send our bundles to prevent rebuilding
+    PackageType::Updater => updater_bundle::bundle_project(settings, bundles)?,
+    _ => {
+      warn!("ignoring {:?}", package_type);
+      return Ok(None);
+    }
+  };
+  Ok(Some(bundle_paths))
+}
+
 /// Check to see if there are icons in the settings struct
 pub fn check_icons(settings: &Settings) -> crate::Result<bool> {
   // make a peekable iterator of the icon_files
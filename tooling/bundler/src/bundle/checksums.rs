@@ -0,0 +1,75 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{common, ChecksumSettings};
+
+use anyhow::Context;
+use log::info;
+use sha2::{Digest, Sha256};
+use std::{
+  fmt::Write as _,
+  fs::File,
+  io::{BufReader, Write},
+  path::{Path, PathBuf},
+};
+
+/// Writes the checksum files of the given release files to the bundle directory.
+///
+/// This is meant to run once every release file exists, including the updater signatures,
+/// delta patches and manifest, so `SHA256SUMS` covers everything that gets published.
+///
+/// Returns the paths of the generated checksum files.
+pub fn write_checksums(
+  checksums: &ChecksumSettings,
+  bundle_dir: &Path,
+  paths: &[PathBuf],
+) -> crate::Result<Vec<PathBuf>> {
+  if !(checksums.sha256_sums || checksums.individual) {
+    return Ok(Vec::new());
+  }
+
+  let mut sums = String::new();
+  let mut checksum_paths = Vec::new();
+
+  // directory bundles such as the macOS app bundle can't be hashed
+  for path in paths.iter().filter(|path| path.is_file()) {
+    let hash = sha256(path)?;
+    let name = path.strip_prefix(bundle_dir).unwrap_or(path);
+    writeln!(
+      sums,
+      "{}  {}",
+      hash,
+      name.to_string_lossy().replace('\\', "/")
+    )
+    .unwrap();
+
+    if checksums.individual {
+      let mut checksum_path = path.clone().into_os_string();
+      checksum_path.push(".sha256");
+      let checksum_path = PathBuf::from(checksum_path);
+      let file_name = path.file_name().unwrap().to_string_lossy();
+      common::create_file(&checksum_path)?
+        .write_all(format!("{}  {}\n", hash, file_name).as_bytes())?;
+      checksum_paths.push(checksum_path);
+    }
+  }
+
+  if checksums.sha256_sums && !sums.is_empty() {
+    let sums_path = bundle_dir.join("SHA256SUMS");
+    info!(action = "Writing"; "{}", sums_path.display());
+    common::create_file(&sums_path)?.write_all(sums.as_bytes())?;
+    checksum_paths.push(sums_path);
+  }
+
+  Ok(checksum_paths)
+}
+
+fn sha256(path: &Path) -> crate::Result<String> {
+  let file =
+    File::open(path).with_context(|| format!("failed to open bundle {}", path.display()))?;
+  let mut hasher = Sha256::new();
+  std::io::copy(&mut BufReader::new(file), &mut hasher)
+    .with_context(|| format!("failed to hash bundle {}", path.display()))?;
+  Ok(hex::encode(hasher.finalize()))
+}
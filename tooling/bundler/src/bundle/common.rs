@@ -8,12 +8,65 @@ use log::debug;
 use std::{
   ffi::OsStr,
   fs::{self, File},
-  io::{self, BufReader, BufWriter},
+  io::{self, BufReader, BufWriter, Write},
   path::Path,
   process::{Command, Output, Stdio},
   sync::{Arc, Mutex},
 };
 
+/// Returns the `SOURCE_DATE_EPOCH` timestamp, used as the modification time of the archived files
+/// to make the bundles reproducible. See <https://reproducible-builds.org/specs/source-date-epoch/>.
+pub fn source_date_epoch() -> Option<u64> {
+  std::env::var("SOURCE_DATE_EPOCH")
+    .ok()
+    .and_then(|epoch| epoch.trim().parse().ok())
+}
+
+/// Creates a gzip encoder, using the `SOURCE_DATE_EPOCH` as modification time if set.
+pub fn gzip_encoder<W: Write>(writer: W) -> crate::Result<libflate::gzip::Encoder<W>> {
+  let encoder = match source_date_epoch() {
+    Some(epoch) => {
+      let header = libflate::gzip::HeaderBuilder::new()
+        .modification_time(epoch as u32)
+        .finish();
+      libflate::gzip::Encoder::with_options(
+        writer,
+        libflate::gzip::EncodeOptions::new().header(header),
+      )?
+    }
+    None => libflate::gzip::Encoder::new(writer)?,
+  };
+  Ok(encoder)
+}
+
+/// Appends a file or directory to a tar archive.
+///
+/// When `SOURCE_DATE_EPOCH` is set, the entry owner and permissions are normalized
+/// and its modification time is set to the epoch, so the archive is reproducible.
+pub fn append_to_tar<W: Write>(
+  builder: &mut tar::Builder<W>,
+  dest_path: &Path,
+  src_path: &Path,
+) -> crate::Result<()> {
+  let metadata = fs::metadata(src_path)?;
+  match source_date_epoch() {
+    Some(epoch) => {
+      let mut header = tar::Header::new_gnu();
+      header.set_metadata_in_mode(&metadata, tar::HeaderMode::Deterministic);
+      header.set_mtime(epoch);
+      if metadata.is_dir() {
+        header.set_size(0);
+        builder.append_data(&mut header, dest_path, io::empty())?;
+      } else {
+        builder.append_data(&mut header, dest_path, File::open(src_path)?)?;
+      }
+    }
+    None if metadata.is_dir() => builder.append_dir(dest_path, src_path)?,
+    None => builder.append_file(dest_path, &mut File::open(src_path)?)?,
+  }
+  Ok(())
+}
+
 /// Returns true if the path has a filename indicating that it is a high-density
 /// "retina" icon.  Specifically, returns true the file stem ends with
 /// "@2x" (a convention specified by the [Apple developer docs](
@@ -137,22 +190,45 @@ pub fn copy_dir(from: &Path, to: &Path) -> crate::Result<()> {
 
 pub trait CommandExt {
   fn output_ok(&mut self) -> crate::Result<Output>;
+  /// Same as [`CommandExt::output_ok`], but logs `***` in place of the secret arguments.
+  fn output_ok_redacted(&mut self, secrets: &[&str]) -> crate::Result<Output>;
+}
+
+/// The command line to log, with the secret arguments replaced by `***`.
+fn command_line(command: &Command, secrets: &[&str]) -> String {
+  command.get_args().fold(
+    command.get_program().to_string_lossy().into_owned(),
+    |acc, arg| {
+      let arg = arg.to_string_lossy();
+      if secrets.contains(&arg.as_ref()) {
+        format!("{} ***", acc)
+      } else {
+        format!("{} {}", acc, arg)
+      }
+    },
+  )
 }
 
 impl CommandExt for Command {
   fn output_ok(&mut self) -> crate::Result<Output> {
+    self.output_ok_redacted(&[])
+  }
+
+  fn output_ok_redacted(&mut self, secrets: &[&str]) -> crate::Result<Output> {
     let program = self.get_program().to_string_lossy().into_owned();
-    debug!(action = "Running"; "Command `{} {}`", program, self.get_args().map(|arg| arg.to_string_lossy()).fold(String::new(), |acc, arg| format!("{} {}", acc, arg)));
+    debug!(action = "Running"; "Command `{}`", command_line(self, secrets));
 
     self.stdout(Stdio::piped());
     self.stderr(Stdio::piped());
 
     let mut child = self.spawn()?;
+    // the output readers inherit the thread name so the log lines can be attributed to a bundle
+    let thread_name = std::thread::current().name().map(ToString::to_string);
 
     let mut stdout = child.stdout.take().map(BufReader::new).unwrap();
     let stdout_lines = Arc::new(Mutex::new(Vec::new()));
     let stdout_lines_ = stdout_lines.clone();
-    std::thread::spawn(move || {
+    let stdout_thread = spawn_named(thread_name.clone(), move || {
       let mut buf = Vec::new();
       let mut lines = stdout_lines_.lock().unwrap();
       loop {
@@ -170,7 +246,7 @@ impl CommandExt for Command {
     let mut stderr = child.stderr.take().map(BufReader::new).unwrap();
     let stderr_lines = Arc::new(Mutex::new(Vec::new()));
     let stderr_lines_ = stderr_lines.clone();
-    std::thread::spawn(move || {
+    let stderr_thread = spawn_named(thread_name.clone(), move || {
       let mut buf = Vec::new();
       let mut lines = stderr_lines_.lock().unwrap();
       loop {
@@ -186,6 +262,9 @@ impl CommandExt for Command {
     });
 
     let status = child.wait()?;
+    // wait for the readers to collect the whole output
+    let _ = stdout_thread.join();
+    let _ = stderr_thread.join();
     let output = Output {
       status,
       stdout: std::mem::take(&mut *stdout_lines.lock().unwrap()),
@@ -195,20 +274,47 @@ impl CommandExt for Command {
     if output.status.success() {
       Ok(output)
     } else {
-      Err(crate::Error::GenericError(format!(
-        "failed to run {}",
-        program
-      )))
+      Err(crate::Error::CommandFailed {
+        program,
+        status: output.status,
+        stdout: String::from_utf8_lossy(&output.stdout).into_owned(),
+        stderr: String::from_utf8_lossy(&output.stderr).into_owned(),
+      })
     }
   }
 }
 
+fn spawn_named<F: FnOnce() + Send + 'static>(
+  name: Option<String>,
+  f: F,
+) -> std::thread::JoinHandle<()> {
+  let mut builder = std::thread::Builder::new();
+  if let Some(name) = name {
+    builder = builder.name(name);
+  }
+  builder.spawn(f).expect("failed to spawn thread")
+}
+
 #[cfg(test)]
 mod tests {
-  use super::{create_file, is_retina};
-  use std::{io::Write, path::PathBuf};
+  use super::{command_line, create_file, is_retina};
+  use std::{io::Write, path::PathBuf, process::Command};
   use tauri_utils::resources::resource_relpath;
 
+  #[test]
+  fn redacts_secret_arguments() {
+    let mut command = Command::new("signtool");
+    command.args(["sign", "/f", "cert.pfx", "/p", "hunter2", "app.exe"]);
+    assert_eq!(
+      command_line(&command, &["hunter2"]),
+      "signtool sign /f cert.pfx /p *** app.exe"
+    );
+    assert_eq!(
+      command_line(&command, &[]),
+      "signtool sign /f cert.pfx /p hunter2 app.exe"
+    );
+  }
+
   #[test]
   fn create_file_with_parent_dirs() {
     let tmp = tempfile::tempdir().expect("Unable to create temp dir");
@@ -4,8 +4,12 @@
 // SPDX-License-Identifier: MIT
 
 use super::{
-  super::{common::CommandExt, path_utils},
-  debian,
+  super::{
+    common::CommandExt,
+    path_utils, register_template_helpers,
+    template::{insert_template_vars, load_template},
+  },
+  debian, sign,
 };
 use crate::Settings;
 use anyhow::Context;
@@ -81,10 +85,19 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
 
   // initialize shell script template.
   let mut handlebars = Handlebars::new();
+  register_template_helpers(&mut handlebars);
   handlebars
-    .register_template_string("appimage", include_str!("templates/appimage"))
+    .register_template_string(
+      "appimage",
+      load_template("appimage", None, include_str!("templates/appimage"))?,
+    )
     .expect("Failed to register template for handlebars");
-  let temp = handlebars.render("appimage", &sh_map)?;
+  let mut data: BTreeMap<&str, serde_json::Value> = sh_map
+    .into_iter()
+    .map(|(name, value)| (name, value.into()))
+    .collect();
+  insert_template_vars(settings, &mut data);
+  let temp = handlebars.render("appimage", &data)?;
 
   // create the shell script file in the target/ folder.
   let sh_file = output_path.join("build_appimage.sh");
@@ -106,9 +119,17 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   // execute the shell script to build the appimage.
   Command::new(&sh_file)
     .current_dir(output_path)
+    .envs(sign::appimage_sign_env(settings))
     .output_ok()
     .context("error running appimage.sh")?;
 
   remove_dir_all(&package_dir)?;
-  Ok(vec![appimage_path])
+
+  let mut paths = vec![appimage_path.clone()];
+  // generated when the update information is set
+  let zsync_path = appimage_path.with_file_name(format!("{}.zsync", appimage_filename));
+  if zsync_path.exists() {
+    paths.push(zsync_path);
+  }
+  Ok(paths)
 }
@@ -20,15 +20,15 @@
 //
 // For cargo-bundle, we put bundle resource files under /usr/lib/package_name/,
 // and then generate the desktop file and control file from the bundle
-// metadata, as well as generating the md5sums file.  Currently we do not
-// generate postinst or prerm files.
+// metadata, as well as generating the md5sums file. The postinst and prerm
+// files are only generated to enable and disable the systemd unit of the
+// service, when one is configured.
 
-use super::super::common;
-use crate::Settings;
+use super::{super::common, sign};
+use crate::{bundle::settings::FileAssociation, Settings};
 use anyhow::Context;
 use heck::AsKebabCase;
 use image::{self, codecs::png::PngDecoder, ImageDecoder};
-use libflate::gzip;
 use log::info;
 use walkdir::WalkDir;
 
@@ -80,11 +80,14 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   let (data_dir, _) = generate_data(settings, &package_dir)
     .with_context(|| "Failed to build data folders and files")?;
   copy_custom_files(settings, &data_dir).with_context(|| "Failed to copy custom files")?;
+  generate_service_file(settings, &data_dir).with_context(|| "Failed to create service file")?;
 
   // Generate control files.
   let control_dir = package_dir.join("control");
   generate_control_file(settings, arch, &control_dir, &data_dir)
     .with_context(|| "Failed to create control file")?;
+  generate_service_scripts(settings, &control_dir)
+    .with_context(|| "Failed to create maintainer scripts")?;
   generate_md5sums(&control_dir, &data_dir).with_context(|| "Failed to create md5sums file")?;
 
   // Generate `debian-binary` file; see
@@ -98,11 +101,13 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     tar_and_gzip_dir(control_dir).with_context(|| "Failed to tar/gzip control directory")?;
   let data_tar_gz_path =
     tar_and_gzip_dir(data_dir).with_context(|| "Failed to tar/gzip data directory")?;
-  create_archive(
-    vec![debian_binary_path, control_tar_gz_path, data_tar_gz_path],
-    &package_path,
-  )
-  .with_context(|| "Failed to create package archive")?;
+  let mut members = vec![debian_binary_path, control_tar_gz_path, data_tar_gz_path];
+  if let Some(key_id) = settings.gpg_key_id() {
+    let signature_path = sign::sign_deb_members(key_id, &members, &package_dir)
+      .with_context(|| "Failed to sign package")?;
+    members.push(signature_path);
+  }
+  create_archive(members, &package_path).with_context(|| "Failed to create package archive")?;
   Ok(vec![package_path])
 }
 
@@ -130,6 +135,9 @@ pub fn generate_data(
   let icons =
     generate_icon_files(settings, &data_dir).with_context(|| "Failed to create icon files")?;
   generate_desktop_file(settings, &data_dir).with_context(|| "Failed to create desktop file")?;
+  if !settings.file_associations().is_empty() {
+    generate_mime_file(settings, &data_dir).with_context(|| "Failed to create MIME type file")?;
+  }
 
   Ok((data_dir, icons))
 }
@@ -153,14 +161,192 @@ fn generate_desktop_file(settings: &Settings, data_dir: &Path) -> crate::Result<
   if !settings.short_description().is_empty() {
     writeln!(file, "Comment={}", settings.short_description())?;
   }
-  writeln!(file, "Exec={}", bin_name)?;
+  let mut mime_types = settings
+    .file_associations()
+    .iter()
+    .map(association_mime_type)
+    .collect::<Vec<_>>();
+  mime_types.extend(
+    settings
+      .deep_link_schemes()
+      .iter()
+      .map(|scheme| format!("x-scheme-handler/{}", scheme)),
+  );
+  if !settings.deep_link_schemes().is_empty() {
+    // the app receives the URLs, including the `file://` ones of the associated files
+    writeln!(file, "Exec={} %U", bin_name)?;
+  } else if !settings.file_associations().is_empty() {
+    writeln!(file, "Exec={} %F", bin_name)?;
+  } else {
+    writeln!(file, "Exec={}", bin_name)?;
+  }
   writeln!(file, "Icon={}", bin_name)?;
+  if !mime_types.is_empty() {
+    writeln!(file, "MimeType={};", mime_types.join(";"))?;
+  }
   writeln!(file, "Name={}", settings.product_name())?;
   writeln!(file, "Terminal=false")?;
   writeln!(file, "Type=Application")?;
   Ok(())
 }
 
+/// Generates the shared MIME-info package defining the file association types
+/// and stores it under the `data_dir`.
+fn generate_mime_file(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
+  // For more information about the format of this file, see
+  // https://specifications.freedesktop.org/shared-mime-info-spec/shared-mime-info-spec-latest.html
+  let mime_file_path = data_dir
+    .join("usr/share/mime/packages")
+    .join(format!("{}.xml", settings.main_binary_name()));
+  let file = &mut common::create_file(&mime_file_path)?;
+  writeln!(file, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+  writeln!(
+    file,
+    r#"<mime-info xmlns="http://www.freedesktop.org/standards/shared-mime-info">"#
+  )?;
+  for association in settings.file_associations() {
+    writeln!(
+      file,
+      r#"  <mime-type type="{}">"#,
+      association_mime_type(association)
+    )?;
+    if let Some(description) = &association.description {
+      writeln!(
+        file,
+        "    <comment>{}</comment>",
+        description.replace('&', "&amp;").replace('<', "&lt;")
+      )?;
+    }
+    for ext in &association.ext {
+      writeln!(file, r#"    <glob pattern="*.{}"/>"#, ext)?;
+    }
+    writeln!(file, "  </mime-type>")?;
+  }
+  writeln!(file, "</mime-info>")?;
+  Ok(())
+}
+
+/// The MIME type of a file association, which defaults to `application/x-<name>`.
+fn association_mime_type(association: &FileAssociation) -> String {
+  match &association.mime_type {
+    Some(mime_type) => mime_type.clone(),
+    None => format!(
+      "application/x-{}",
+      AsKebabCase(
+        association
+          .name
+          .as_deref()
+          .or_else(|| association.ext.first().map(String::as_str))
+          .unwrap_or("document")
+      )
+    ),
+  }
+}
+
+/// Generates the systemd unit of the service, if any, and stores it under the `data_dir`.
+pub fn generate_service_file(settings: &Settings, data_dir: &Path) -> crate::Result<()> {
+  let service = match settings.service() {
+    Some(service) => service,
+    None => return Ok(()),
+  };
+  // For more information about the format of this file, see
+  // https://www.freedesktop.org/software/systemd/man/systemd.service.html
+  let unit_dir = if service.user_unit {
+    "usr/lib/systemd/user"
+  } else {
+    "usr/lib/systemd/system"
+  };
+  let unit_path = data_dir
+    .join(unit_dir)
+    .join(format!("{}.service", settings.service_name()));
+  let file = &mut common::create_file(&unit_path)?;
+  writeln!(file, "[Unit]")?;
+  writeln!(
+    file,
+    "Description={}",
+    service
+      .description
+      .as_deref()
+      .unwrap_or_else(|| settings.short_description())
+      .trim()
+  )?;
+  if !service.user_unit {
+    writeln!(file, "After=network.target")?;
+  }
+  writeln!(file, "\n[Service]")?;
+  let mut exec_start = format!("/usr/bin/{}", settings.main_binary_name());
+  for arg in &service.args {
+    exec_start.push(' ');
+    exec_start.push_str(arg);
+  }
+  writeln!(file, "ExecStart={}", exec_start)?;
+  writeln!(file, "Restart=on-failure")?;
+  writeln!(file, "\n[Install]")?;
+  writeln!(
+    file,
+    "WantedBy={}",
+    if service.user_unit {
+      "default.target"
+    } else {
+      "multi-user.target"
+    }
+  )?;
+  Ok(())
+}
+
+/// The shell commands enabling the service on install, and disabling it on removal.
+pub fn service_commands(settings: &Settings) -> Option<(String, String)> {
+  let service = settings.service()?;
+  let unit = format!("{}.service", settings.service_name());
+  Some(if service.user_unit {
+    // user units can't be started for every user from the package scripts
+    (
+      format!("systemctl --global enable {}", unit),
+      format!("systemctl --global disable {} || true", unit),
+    )
+  } else {
+    (
+      format!(
+        "if [ -d /run/systemd/system ]; then\n  systemctl daemon-reload\n  systemctl enable --now {}\nfi",
+        unit
+      ),
+      format!(
+        "if [ -d /run/systemd/system ]; then\n  systemctl disable --now {} || true\nfi",
+        unit
+      ),
+    )
+  })
+}
+
+/// Generates the postinst and prerm scripts enabling the service, if any, under the `control_dir`.
+fn generate_service_scripts(settings: &Settings, control_dir: &Path) -> crate::Result<()> {
+  if let Some((enable, disable)) = service_commands(settings) {
+    create_script(
+      &control_dir.join("postinst"),
+      &format!(
+        "#!/bin/sh\nset -e\nif [ \"$1\" = configure ]; then\n{}\nfi\n",
+        enable
+      ),
+    )?;
+    create_script(
+      &control_dir.join("prerm"),
+      &format!(
+        "#!/bin/sh\nset -e\nif [ \"$1\" = remove ]; then\n{}\nfi\n",
+        disable
+      ),
+    )?;
+  }
+  Ok(())
+}
+
+/// Creates an executable maintainer script.
+fn create_script(path: &Path, data: &str) -> crate::Result<()> {
+  use std::os::unix::fs::PermissionsExt;
+  create_file_with_data(path, data)?;
+  fs::set_permissions(path, fs::Permissions::from_mode(0o755))?;
+  Ok(())
+}
+
 /// Generates the debian control file and stores it under the `control_dir`.
 fn generate_control_file(
   settings: &Settings,
@@ -177,8 +363,14 @@ fn generate_control_file(
   writeln!(file, "Architecture: {}", arch)?;
   // Installed-Size must be divided by 1024, see https://www.debian.org/doc/debian-policy/ch-controlfields.html#installed-size
   writeln!(file, "Installed-Size: {}", total_dir_size(data_dir)? / 1024)?;
-  let authors = settings.authors_comma_separated().unwrap_or_default();
-  writeln!(file, "Maintainer: {}", authors)?;
+  let maintainer = match &settings.deb().maintainer {
+    Some(maintainer) => maintainer.clone(),
+    None => settings.authors_comma_separated().unwrap_or_default(),
+  };
+  writeln!(file, "Maintainer: {}", maintainer)?;
+  if let Some(section) = &settings.deb().section {
+    writeln!(file, "Section: {}", section)?;
+  }
   if !settings.homepage_url().is_empty() {
     writeln!(file, "Homepage: {}", settings.homepage_url())?;
   }
@@ -203,7 +395,11 @@ fn generate_control_file(
       writeln!(file, " {}", line)?;
     }
   }
-  writeln!(file, "Priority: optional")?;
+  writeln!(
+    file,
+    "Priority: {}",
+    settings.deb().priority.as_deref().unwrap_or("optional")
+  )?;
   file.flush()?;
   Ok(())
 }
@@ -213,7 +409,7 @@ fn generate_control_file(
 fn generate_md5sums(control_dir: &Path, data_dir: &Path) -> crate::Result<()> {
   let md5sums_path = control_dir.join("md5sums");
   let mut md5sums_file = common::create_file(&md5sums_path)?;
-  for entry in WalkDir::new(data_dir) {
+  for entry in WalkDir::new(data_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
     let entry = entry?;
     let path = entry.path();
     if path.is_dir() {
@@ -329,19 +525,15 @@ fn total_dir_size(dir: &Path) -> crate::Result<u64> {
 fn create_tar_from_dir<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> crate::Result<W> {
   let src_dir = src_dir.as_ref();
   let mut tar_builder = tar::Builder::new(dest_file);
-  for entry in WalkDir::new(src_dir) {
+  // sorted to make the archive reproducible
+  for entry in WalkDir::new(src_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
     let entry = entry?;
     let src_path = entry.path();
     if src_path == src_dir {
       continue;
     }
     let dest_path = src_path.strip_prefix(src_dir)?;
-    if entry.file_type().is_dir() {
-      tar_builder.append_dir(dest_path, src_path)?;
-    } else {
-      let mut src_file = fs::File::open(src_path)?;
-      tar_builder.append_file(dest_path, &mut src_file)?;
-    }
+    common::append_to_tar(&mut tar_builder, dest_path, src_path)?;
   }
   let dest_file = tar_builder.into_inner()?;
   Ok(dest_file)
@@ -354,7 +546,7 @@ fn tar_and_gzip_dir<P: AsRef<Path>>(src_dir: P) -> crate::Result<PathBuf> {
   let src_dir = src_dir.as_ref();
   let dest_path = src_dir.with_extension("tar.gz");
   let dest_file = common::create_file(&dest_path)?;
-  let gzip_encoder = gzip::Encoder::new(dest_file)?;
+  let gzip_encoder = common::gzip_encoder(dest_file)?;
   let gzip_encoder = create_tar_from_dir(src_dir, gzip_encoder)?;
   let mut dest_file = gzip_encoder.finish().into_result()?;
   dest_file.flush()?;
@@ -365,8 +557,29 @@ fn tar_and_gzip_dir<P: AsRef<Path>>(src_dir: P) -> crate::Result<PathBuf> {
 /// given destination path.
 fn create_archive(srcs: Vec<PathBuf>, dest: &Path) -> crate::Result<()> {
   let mut builder = ar::Builder::new(common::create_file(dest)?);
-  for path in &srcs {
-    builder.append_path(path)?;
+  match common::source_date_epoch() {
+    Some(epoch) => {
+      for path in &srcs {
+        let file = File::open(path)?;
+        let mut header = ar::Header::new(
+          path
+            .file_name()
+            .expect("failed to get file name")
+            .to_string_lossy()
+            .as_bytes()
+            .to_vec(),
+          file.metadata()?.len(),
+        );
+        header.set_mtime(epoch);
+        header.set_mode(0o644);
+        builder.append(&header, file)?;
+      }
+    }
+    None => {
+      for path in &srcs {
+        builder.append_path(path)?;
+      }
+    }
   }
   builder.into_inner()?.flush()?;
   Ok(())
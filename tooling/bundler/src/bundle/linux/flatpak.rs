@@ -0,0 +1,291 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The Flatpak bundle is described by a flatpak-builder manifest with a single module
+// that copies the files laid out by the Debian bundler (see `debian::generate_data`)
+// into the `/app` prefix, next to an AppStream metainfo file describing the app.
+//
+// bundle/flatpak/
+//     com.tauri.example.json           # The flatpak-builder manifest
+//     com.tauri.example/data/usr/...   # The module sources
+//     foobar_1.2.3_x86_64.flatpak      # The single-file bundle (when `buildBundle` is set)
+
+use super::{super::common, debian};
+use crate::{bundle::common::CommandExt, Settings};
+use anyhow::Context;
+use log::info;
+use serde_json::json;
+
+use std::{
+  fs,
+  io::Write,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+const DEFAULT_RUNTIME: &str = "org.gnome.Platform";
+const DEFAULT_RUNTIME_VERSION: &str = "43";
+const DEFAULT_SDK: &str = "org.gnome.Sdk";
+const DEFAULT_FINISH_ARGS: &[&str] = &[
+  "--share=ipc",
+  "--socket=x11",
+  "--socket=wayland",
+  "--device=dri",
+  "--share=network",
+];
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the Flatpak manifest and bundle were created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    other => other,
+  };
+  let flatpak = settings.flatpak();
+  let app_id = app_id(settings.bundle_identifier());
+  let bin_name = settings.main_binary_name();
+
+  let base_dir = settings.project_out_directory().join("bundle/flatpak");
+  if base_dir.exists() {
+    fs::remove_dir_all(&base_dir).with_context(|| "Failed to remove old flatpak bundle")?;
+  }
+  let package_dir = base_dir.join(&app_id);
+  let manifest_path = base_dir.join(format!("{}.json", app_id));
+
+  info!(action = "Bundling"; "{} ({})", app_id, manifest_path.display());
+
+  let (data_dir, _) = debian::generate_data(settings, &package_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+  generate_metainfo_file(settings, &app_id, &data_dir)
+    .with_context(|| "Failed to create metainfo file")?;
+
+  let manifest = generate_manifest(settings, &app_id, data_dir.strip_prefix(&base_dir)?);
+  let mut file = common::create_file(&manifest_path)?;
+  serde_json::to_writer_pretty(&mut file, &manifest)?;
+  file.flush()?;
+
+  let mut paths = vec![manifest_path.clone()];
+
+  if flatpak.build_bundle {
+    let bundle_path = base_dir.join(format!(
+      "{}_{}_{}.flatpak",
+      bin_name,
+      settings.version_string(),
+      arch
+    ));
+
+    info!(action = "Running"; "flatpak-builder to produce {}", bundle_path.display());
+
+    Command::new("flatpak-builder")
+      .arg("--force-clean")
+      .arg(format!("--arch={}", arch))
+      .arg("--repo=repo")
+      .arg("build")
+      .arg(&manifest_path)
+      .current_dir(&base_dir)
+      .output_ok()
+      .context("error running flatpak-builder, make sure it is installed")?;
+
+    Command::new("flatpak")
+      .arg("build-bundle")
+      .arg(format!("--arch={}", arch))
+      .arg("repo")
+      .arg(&bundle_path)
+      .arg(&app_id)
+      .current_dir(&base_dir)
+      .output_ok()
+      .context("error running flatpak build-bundle")?;
+
+    paths.push(bundle_path);
+  }
+
+  Ok(paths)
+}
+
+/// Generates the flatpak-builder manifest, with the module sources at `source_path`
+/// relative to the manifest.
+fn generate_manifest(settings: &Settings, app_id: &str, source_path: &Path) -> serde_json::Value {
+  let flatpak = settings.flatpak();
+  let bin_name = settings.main_binary_name();
+  let finish_args = match &flatpak.finish_args {
+    Some(args) => args.clone(),
+    None => DEFAULT_FINISH_ARGS.iter().map(|a| a.to_string()).collect(),
+  };
+  json!({
+    "app-id": app_id,
+    "runtime": flatpak.runtime.as_deref().unwrap_or(DEFAULT_RUNTIME),
+    "runtime-version": flatpak.runtime_version.as_deref().unwrap_or(DEFAULT_RUNTIME_VERSION),
+    "sdk": flatpak.sdk.as_deref().unwrap_or(DEFAULT_SDK),
+    "command": bin_name,
+    "finish-args": finish_args,
+    // the desktop file and icons are named after the binary by the Debian layout
+    "rename-desktop-file": format!("{}.desktop", bin_name),
+    "rename-icon": bin_name,
+    "modules": [{
+      "name": bin_name,
+      "buildsystem": "simple",
+      "build-commands": ["cp -a usr/. /app/"],
+      "sources": [{
+        "type": "dir",
+        "path": source_path,
+      }],
+    }],
+  })
+}
+
+/// Derives a valid Flatpak application ID from the bundle identifier.
+///
+/// Each element of the ID may only contain `[A-Za-z0-9_]` and must not start with a digit,
+/// and the ID needs at least three elements.
+fn app_id(identifier: &str) -> String {
+  let mut elements = identifier
+    .split('.')
+    .filter(|element| !element.is_empty())
+    .map(|element| {
+      let element = element.replace(|c: char| !c.is_ascii_alphanumeric(), "_");
+      if element.starts_with(|c: char| c.is_ascii_digit()) {
+        format!("_{}", element)
+      } else {
+        element
+      }
+    })
+    .collect::<Vec<_>>();
+  while elements.len() < 3 {
+    elements.push("app".into());
+  }
+  elements.join(".")
+}
+
+/// Generates the AppStream metainfo file and stores it under the `data_dir`.
+fn generate_metainfo_file(settings: &Settings, app_id: &str, data_dir: &Path) -> crate::Result<()> {
+  // For more information about the format of this file, see
+  // https://www.freedesktop.org/software/appstream/docs/chap-Quickstart.html
+  let metainfo_path = data_dir
+    .join("usr/share/metainfo")
+    .join(format!("{}.metainfo.xml", app_id));
+  let file = &mut common::create_file(&metainfo_path)?;
+  writeln!(file, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+  writeln!(file, r#"<component type="desktop-application">"#)?;
+  writeln!(file, "  <id>{}</id>", app_id)?;
+  writeln!(file, "  <name>{}</name>", escape(settings.product_name()))?;
+  writeln!(
+    file,
+    "  <summary>{}</summary>",
+    escape(settings.short_description().trim())
+  )?;
+  writeln!(file, "  <metadata_license>CC0-1.0</metadata_license>")?;
+  if let Some(license) = &settings.flatpak().license {
+    writeln!(
+      file,
+      "  <project_license>{}</project_license>",
+      escape(license)
+    )?;
+  }
+  if let Some(description) = settings.long_description() {
+    writeln!(file, "  <description>")?;
+    writeln!(file, "    <p>{}</p>", escape(description.trim()))?;
+    writeln!(file, "  </description>")?;
+  }
+  if !settings.homepage_url().is_empty() {
+    writeln!(
+      file,
+      r#"  <url type="homepage">{}</url>"#,
+      escape(settings.homepage_url())
+    )?;
+  }
+  writeln!(
+    file,
+    r#"  <launchable type="desktop-id">{}.desktop</launchable>"#,
+    app_id
+  )?;
+  writeln!(file, "  <releases>")?;
+  writeln!(
+    file,
+    r#"    <release version="{}"/>"#,
+    escape(settings.version_string())
+  )?;
+  writeln!(file, "  </releases>")?;
+  writeln!(file, "</component>")?;
+  Ok(())
+}
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{app_id, generate_manifest, generate_metainfo_file};
+  use crate::bundle::settings::{test_settings, BundleSettings, FlatpakSettings};
+  use serde_json::json;
+  use std::{fs, path::Path};
+
+  #[test]
+  fn derives_app_id() {
+    assert_eq!(app_id("com.tauri.app"), "com.tauri.app");
+    assert_eq!(app_id("com.tauri-apps.2d-app"), "com.tauri_apps._2d_app");
+    assert_eq!(app_id("tauri..app"), "tauri.app.app");
+  }
+
+  #[test]
+  fn generates_manifest() {
+    let settings = test_settings(
+      "x86_64-unknown-linux-gnu",
+      BundleSettings {
+        flatpak: FlatpakSettings {
+          finish_args: Some(vec!["--share=ipc".into()]),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    );
+    assert_eq!(
+      generate_manifest(&settings, "com.tauri.app", Path::new("com.tauri.app/data")),
+      json!({
+        "app-id": "com.tauri.app",
+        "runtime": "org.gnome.Platform",
+        "runtime-version": "43",
+        "sdk": "org.gnome.Sdk",
+        "command": "tauri-app",
+        "finish-args": ["--share=ipc"],
+        "rename-desktop-file": "tauri-app.desktop",
+        "rename-icon": "tauri-app",
+        "modules": [{
+          "name": "tauri-app",
+          "buildsystem": "simple",
+          "build-commands": ["cp -a usr/. /app/"],
+          "sources": [{ "type": "dir", "path": "com.tauri.app/data" }],
+        }],
+      })
+    );
+  }
+
+  #[test]
+  fn generates_metainfo_file() {
+    let tmp = tempfile::tempdir().expect("unable to create temp dir");
+    let settings = test_settings(
+      "x86_64-unknown-linux-gnu",
+      BundleSettings {
+        long_description: Some("Tauri <3".into()),
+        ..Default::default()
+      },
+    );
+    generate_metainfo_file(&settings, "com.tauri.app", tmp.path()).unwrap();
+    let metainfo = fs::read_to_string(
+      tmp
+        .path()
+        .join("usr/share/metainfo/com.tauri.app.metainfo.xml"),
+    )
+    .unwrap();
+    assert!(metainfo.contains(
+      "  <id>com.tauri.app</id>\n  <name>Tauri App</name>\n  <summary>A Tauri App</summary>\n"
+    ));
+    assert!(metainfo.contains("    <p>Tauri &lt;3</p>\n"));
+    assert!(metainfo.contains(r#"    <release version="1.2.3"/>"#));
+  }
+}
@@ -5,4 +5,7 @@
 
 pub mod appimage;
 pub mod debian;
+pub mod flatpak;
 pub mod rpm;
+pub mod sign;
+pub mod snap;
@@ -3,12 +3,239 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::Settings;
+// The RPM package is built by generating a spec file that copies the files laid out
+// by the Debian bundler (see `debian::generate_data`) and running `rpmbuild` on it.
 
-use std::path::PathBuf;
+use super::{super::common, debian, sign};
+use crate::{bundle::common::CommandExt, Settings};
+use anyhow::Context;
+use heck::AsKebabCase;
+use log::info;
+use walkdir::WalkDir;
+
+use std::{
+  fs,
+  io::Write,
+  path::{Path, PathBuf},
+  process::Command,
+};
 
 /// Bundles the project.
-/// Not implemented yet.
-pub fn bundle_project(_settings: &Settings) -> crate::Result<Vec<PathBuf>> {
-  unimplemented!();
+/// Returns a vector of PathBuf that shows where the RPM was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    "arm" => "armhfp",
+    other => other,
+  };
+  let rpm = settings.rpm();
+  // RPM versions can't contain hyphens, which are used by semver pre-releases
+  let version = settings.version_string().replace('-', "~");
+  let package_base_name = format!(
+    "{}-{}-{}.{}",
+    settings.main_binary_name(),
+    version,
+    rpm.release,
+    arch
+  );
+  let package_name = format!("{}.rpm", package_base_name);
+
+  let base_dir = settings.project_out_directory().join("bundle/rpm");
+  let package_dir = base_dir.join(&package_base_name);
+  if package_dir.exists() {
+    fs::remove_dir_all(&package_dir)
+      .with_context(|| format!("Failed to remove old {}", package_base_name))?;
+  }
+  let package_path = base_dir.join(&package_name);
+
+  info!(action = "Bundling"; "{} ({})", package_name, package_path.display());
+
+  let (data_dir, _) = debian::generate_data(settings, &package_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+  debian::generate_service_file(settings, &data_dir)
+    .with_context(|| "Failed to create service file")?;
+
+  let spec_path = package_dir.join(format!("{}.spec", settings.main_binary_name()));
+  generate_spec_file(settings, arch, &version, &spec_path, &data_dir)
+    .with_context(|| "Failed to create spec file")?;
+
+  let mut rpmbuild = Command::new("rpmbuild");
+  rpmbuild.arg("-bb");
+  if common::source_date_epoch().is_some() {
+    // rpmbuild reads the `SOURCE_DATE_EPOCH` itself, but only uses it when asked to
+    rpmbuild
+      .arg("--define")
+      .arg("use_source_date_epoch_as_buildtime 1")
+      .arg("--define")
+      .arg("clamp_mtime_to_source_date_epoch 1");
+  }
+  rpmbuild
+    .arg("--define")
+    .arg(format!(
+      "_topdir {}",
+      package_dir.join("rpmbuild").display()
+    ))
+    .arg("--define")
+    .arg(format!("_rpmdir {}", base_dir.display()))
+    .arg("--define")
+    .arg(format!("_build_name_fmt {}", package_name))
+    .arg("--target")
+    .arg(arch)
+    .arg(&spec_path)
+    .output_ok()
+    .context("error running rpmbuild, make sure it is installed")?;
+
+  if let Some(key_id) = settings.gpg_key_id() {
+    sign::sign_rpm(key_id, &package_path).with_context(|| "Failed to sign package")?;
+  }
+
+  Ok(vec![package_path])
+}
+
+/// Generates the RPM spec file.
+fn generate_spec_file(
+  settings: &Settings,
+  arch: &str,
+  version: &str,
+  spec_path: &Path,
+  data_dir: &Path,
+) -> crate::Result<()> {
+  // For more information about the format of this file, see
+  // https://rpm-software-management.github.io/rpm/manual/spec.html
+  let rpm = settings.rpm();
+  let mut file = common::create_file(spec_path)?;
+  writeln!(file, "Name: {}", AsKebabCase(settings.product_name()))?;
+  writeln!(file, "Version: {}", version)?;
+  writeln!(file, "Release: {}", rpm.release)?;
+  let summary = settings.short_description().trim();
+  writeln!(
+    file,
+    "Summary: {}",
+    if summary.is_empty() {
+      settings.product_name()
+    } else {
+      summary
+    }
+  )?;
+  writeln!(
+    file,
+    "License: {}",
+    rpm.license.as_deref().unwrap_or("Unspecified")
+  )?;
+  if !settings.homepage_url().is_empty() {
+    writeln!(file, "URL: {}", settings.homepage_url())?;
+  }
+  if let Some(packager) = settings.authors_comma_separated() {
+    writeln!(file, "Packager: {}", packager)?;
+  }
+  writeln!(file, "BuildArch: {}", arch)?;
+  for dependency in rpm.depends.iter().flatten() {
+    writeln!(file, "Requires: {}", dependency)?;
+  }
+  // the binaries are already built, don't let rpmbuild strip them or look for debug information
+  writeln!(file, "AutoReqProv: no")?;
+  writeln!(file, "%global debug_package %{{nil}}")?;
+  writeln!(file, "%global __os_install_post %{{nil}}")?;
+
+  writeln!(file, "\n%description")?;
+  let description = settings
+    .long_description()
+    .unwrap_or_else(|| settings.short_description())
+    .trim();
+  writeln!(
+    file,
+    "{}",
+    if description.is_empty() {
+      "(none)"
+    } else {
+      description
+    }
+  )?;
+
+  writeln!(file, "\n%install")?;
+  writeln!(file, "mkdir -p %{{buildroot}}")?;
+  writeln!(file, "cp -a \"{}/.\" %{{buildroot}}/", data_dir.display())?;
+
+  writeln!(file, "\n%files")?;
+  // the package owns the resource directory, so it is removed with the package
+  let resource_dir = Path::new("usr/lib").join(settings.main_binary_name());
+  for entry in WalkDir::new(data_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+    let entry = entry?;
+    let path = entry.path().strip_prefix(data_dir)?;
+    if entry.file_type().is_file() {
+      writeln!(file, "\"/{}\"", path.display())?;
+    } else if entry.file_type().is_dir() && path.starts_with(&resource_dir) {
+      writeln!(file, "%dir \"/{}\"", path.display())?;
+    }
+  }
+
+  let service_commands = debian::service_commands(settings);
+  if service_commands.is_some() || rpm.post_install_script.is_some() {
+    writeln!(file, "\n%post")?;
+  }
+  if let Some((enable, _)) = &service_commands {
+    // `$1` is 1 on the first installation
+    writeln!(file, "if [ $1 -eq 1 ]; then\n{}\nfi", enable)?;
+  }
+  if let Some(script) = &rpm.post_install_script {
+    write!(file, "{}", read_script(script)?)?;
+  }
+  if let Some((_, disable)) = &service_commands {
+    writeln!(file, "\n%preun")?;
+    // `$1` is 0 when the package is removed, rather than upgraded
+    writeln!(file, "if [ $1 -eq 0 ]; then\n{}\nfi", disable)?;
+  }
+  if let Some(script) = &rpm.post_remove_script {
+    writeln!(file, "\n%postun")?;
+    write!(file, "{}", read_script(script)?)?;
+  }
+
+  file.flush()?;
+  Ok(())
+}
+
+fn read_script(path: &Path) -> crate::Result<String> {
+  let mut script = fs::read_to_string(path)
+    .with_context(|| format!("Failed to read script {}", path.display()))?;
+  if !script.ends_with('\n') {
+    script.push('\n');
+  }
+  Ok(script)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::generate_spec_file;
+  use crate::bundle::settings::{test_settings, BundleSettings};
+  use std::fs;
+
+  #[test]
+  fn generates_spec_file() {
+    let tmp = tempfile::tempdir().expect("unable to create temp dir");
+    let data_dir = tmp.path().join("data");
+    fs::create_dir_all(data_dir.join("usr/bin")).unwrap();
+    fs::write(data_dir.join("usr/bin/tauri-app"), "").unwrap();
+    fs::create_dir_all(data_dir.join("usr/lib/tauri-app/assets")).unwrap();
+    fs::write(data_dir.join("usr/lib/tauri-app/assets/logo.png"), "").unwrap();
+
+    let settings = test_settings(
+      "x86_64-unknown-linux-gnu",
+      BundleSettings {
+        short_description: Some(String::new()),
+        ..Default::default()
+      },
+    );
+    let spec_path = tmp.path().join("tauri-app.spec");
+    generate_spec_file(&settings, "x86_64", "1.2.3", &spec_path, &data_dir).unwrap();
+    let spec = fs::read_to_string(spec_path).unwrap();
+
+    assert!(spec.contains("Name: tauri-app\n"));
+    assert!(spec.contains("Version: 1.2.3\n"));
+    // the summary falls back to the product name
+    assert!(spec.contains("Summary: Tauri App\n"));
+    assert!(spec.contains("BuildArch: x86_64\n"));
+    assert!(spec.contains(
+      "%files\n\"/usr/bin/tauri-app\"\n%dir \"/usr/lib/tauri-app\"\n%dir \"/usr/lib/tauri-app/assets\"\n\"/usr/lib/tauri-app/assets/logo.png\"\n"
+    ));
+  }
 }
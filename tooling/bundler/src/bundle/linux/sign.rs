@@ -0,0 +1,123 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// GPG signing of the Linux packages.
+// The key is selected with `bundle > gpg > keyId` (or the `TAURI_GPG_KEY_ID` environment variable)
+// and its passphrase is read from the `TAURI_GPG_PASSPHRASE` environment variable.
+//
+// - .deb: a `_gpgorigin` member is appended to the archive, as done by `debsigs`.
+// - .rpm: the package header is signed with `rpmsign --addsign`.
+// - .AppImage: the signature is embedded by `appimagetool` (see `appimage_sign_env`).
+
+use crate::{bundle::common::CommandExt, Settings};
+use anyhow::Context;
+use log::info;
+use std::{
+  fs::{self, File},
+  io::{self, Write},
+  path::{Path, PathBuf},
+  process::Command,
+};
+use tempfile::NamedTempFile;
+
+/// The environment variable holding the passphrase of the signing key.
+pub const PASSPHRASE_ENV: &str = "TAURI_GPG_PASSPHRASE";
+
+/// Writes the passphrase to a temporary file, so it isn't exposed on the command line.
+fn passphrase_file() -> crate::Result<Option<NamedTempFile>> {
+  match std::env::var(PASSPHRASE_ENV) {
+    Ok(passphrase) => {
+      let mut file = NamedTempFile::new()?;
+      file.write_all(passphrase.as_bytes())?;
+      file.flush()?;
+      Ok(Some(file))
+    }
+    Err(_) => Ok(None),
+  }
+}
+
+/// The gpg arguments to sign without prompting for the passphrase.
+fn gpg_passphrase_args(passphrase_file: &Option<NamedTempFile>) -> Vec<String> {
+  match passphrase_file {
+    Some(file) => vec![
+      "--batch".into(),
+      "--pinentry-mode".into(),
+      "loopback".into(),
+      "--passphrase-file".into(),
+      file.path().display().to_string(),
+    ],
+    None => Vec::new(),
+  }
+}
+
+/// Generates the `_gpgorigin` member of a Debian package, a detached signature of the
+/// concatenation of the other members, and returns its path.
+pub fn sign_deb_members(
+  key_id: &str,
+  members: &[PathBuf],
+  dest_dir: &Path,
+) -> crate::Result<PathBuf> {
+  info!(action = "Signing"; "Debian package with key {}", key_id);
+
+  let content_path = dest_dir.join("_gpgcontent");
+  let mut content = File::create(&content_path)?;
+  for member in members {
+    io::copy(&mut File::open(member)?, &mut content)?;
+  }
+  content.flush()?;
+
+  let signature_path = dest_dir.join("_gpgorigin");
+  let passphrase_file = passphrase_file()?;
+  Command::new("gpg")
+    .args(gpg_passphrase_args(&passphrase_file))
+    .arg("--yes")
+    .arg("--openpgp")
+    .arg("--local-user")
+    .arg(key_id)
+    .arg("--detach-sign")
+    .arg("--output")
+    .arg(&signature_path)
+    .arg(&content_path)
+    .output_ok()
+    .context("error running gpg, make sure it is installed")?;
+  fs::remove_file(&content_path)?;
+
+  Ok(signature_path)
+}
+
+/// Signs the RPM package in place.
+pub fn sign_rpm(key_id: &str, package_path: &Path) -> crate::Result<()> {
+  info!(action = "Signing"; "{} with key {}", package_path.display(), key_id);
+
+  let passphrase_file = passphrase_file()?;
+  let mut rpmsign = Command::new("rpmsign");
+  rpmsign
+    .arg("--addsign")
+    .arg("--define")
+    .arg(format!("_gpg_name {}", key_id));
+  if passphrase_file.is_some() {
+    rpmsign.arg("--define").arg(format!(
+      "_gpg_sign_cmd_extra_args {}",
+      gpg_passphrase_args(&passphrase_file).join(" ")
+    ));
+  }
+  rpmsign
+    .arg(package_path)
+    .output_ok()
+    .context("error running rpmsign, make sure it is installed")?;
+  Ok(())
+}
+
+/// The environment variables that make `appimagetool` embed a signature in the AppImage.
+pub fn appimage_sign_env(settings: &Settings) -> Vec<(&'static str, String)> {
+  let mut env = Vec::new();
+  if let Some(key_id) = settings.gpg_key_id() {
+    env.push(("SIGN", "1".into()));
+    env.push(("SIGN_KEY", key_id.into()));
+    if let Ok(passphrase) = std::env::var(PASSPHRASE_ENV) {
+      env.push(("APPIMAGETOOL_SIGN_PASSPHRASE", passphrase));
+    }
+  }
+  env
+}
@@ -0,0 +1,250 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The Snap package is described by a `snapcraft.yaml` with a single part that dumps
+// the files laid out by the Debian bundler (see `debian::generate_data`) into the snap.
+//
+// bundle/snap/
+//     snap/snapcraft.yaml          # The snapcraft project
+//     data/usr/...                 # The part sources
+//     foobar_1.2.3_amd64.snap      # The package (when `buildSnap` is set)
+
+use super::{super::common, debian};
+use crate::{
+  bundle::{common::CommandExt, settings::SnapConfinement},
+  Settings,
+};
+use anyhow::Context;
+use heck::AsKebabCase;
+use log::info;
+
+use std::{
+  fs,
+  io::Write,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+const DEFAULT_BASE: &str = "core22";
+const DEFAULT_PLUGS: &[&str] = &[
+  "network",
+  "home",
+  "desktop",
+  "desktop-legacy",
+  "wayland",
+  "x11",
+  "opengl",
+];
+const DEFAULT_STAGE_PACKAGES: &[&str] = &["libwebkit2gtk-4.0-37"];
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the snapcraft project and package were created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86" => "i386",
+    "x86_64" => "amd64",
+    "arm" => "armhf",
+    "aarch64" => "arm64",
+    other => other,
+  };
+  // snap names can only contain lowercase letters, numbers and hyphens
+  let name = AsKebabCase(settings.product_name())
+    .to_string()
+    .to_lowercase();
+
+  let base_dir = settings.project_out_directory().join("bundle/snap");
+  if base_dir.exists() {
+    fs::remove_dir_all(&base_dir).with_context(|| "Failed to remove old snap bundle")?;
+  }
+  let snapcraft_path = base_dir.join("snap/snapcraft.yaml");
+
+  info!(action = "Bundling"; "{} ({})", name, snapcraft_path.display());
+
+  let (data_dir, icons) = debian::generate_data(settings, &base_dir)
+    .with_context(|| "Failed to build data folders and files")?;
+
+  // the desktop file must point to the icon inside the snap
+  if let Some(icon) = icons
+    .iter()
+    .filter(|i| i.width == i.height)
+    .max_by_key(|i| i.width)
+  {
+    let desktop_file_path = data_dir
+      .join("usr/share/applications")
+      .join(format!("{}.desktop", settings.main_binary_name()));
+    let desktop_file = fs::read_to_string(&desktop_file_path)?;
+    fs::write(
+      &desktop_file_path,
+      desktop_file.replace(
+        &format!("Icon={}\n", settings.main_binary_name()),
+        &format!(
+          "Icon=${{SNAP}}/{}\n",
+          icon.path.strip_prefix(&data_dir)?.display()
+        ),
+      ),
+    )?;
+  }
+
+  generate_snapcraft_file(settings, &name, &snapcraft_path)
+    .with_context(|| "Failed to create snapcraft.yaml")?;
+
+  let mut paths = vec![snapcraft_path];
+
+  if settings.snap().build_snap {
+    let snap_path = base_dir.join(format!(
+      "{}_{}_{}.snap",
+      name,
+      settings.version_string(),
+      arch
+    ));
+
+    info!(action = "Running"; "snapcraft to produce {}", snap_path.display());
+
+    Command::new("snapcraft")
+      .arg("--output")
+      .arg(&snap_path)
+      .current_dir(&base_dir)
+      .output_ok()
+      .context("error running snapcraft, make sure it is installed")?;
+
+    paths.push(snap_path);
+  }
+
+  Ok(paths)
+}
+
+/// Generates the `snapcraft.yaml` file.
+fn generate_snapcraft_file(
+  settings: &Settings,
+  name: &str,
+  snapcraft_path: &Path,
+) -> crate::Result<()> {
+  // For more information about the format of this file, see
+  // https://snapcraft.io/docs/snapcraft-yaml-reference
+  let snap = settings.snap();
+  let bin_name = settings.main_binary_name();
+  let mut file = common::create_file(snapcraft_path)?;
+  writeln!(file, "name: {}", name)?;
+  writeln!(
+    file,
+    "base: {}",
+    snap.base.as_deref().unwrap_or(DEFAULT_BASE)
+  )?;
+  writeln!(file, "version: {}", quote(settings.version_string()))?;
+  writeln!(
+    file,
+    "summary: {}",
+    quote(settings.short_description().trim())
+  )?;
+  writeln!(
+    file,
+    "description: {}",
+    quote(
+      settings
+        .long_description()
+        .unwrap_or_else(|| settings.short_description())
+        .trim()
+    )
+  )?;
+  writeln!(
+    file,
+    "grade: {}",
+    if snap.confinement == SnapConfinement::Devmode {
+      "devel"
+    } else {
+      "stable"
+    }
+  )?;
+  writeln!(
+    file,
+    "confinement: {}",
+    match snap.confinement {
+      SnapConfinement::Strict => "strict",
+      SnapConfinement::Classic => "classic",
+      SnapConfinement::Devmode => "devmode",
+    }
+  )?;
+
+  writeln!(file, "\napps:")?;
+  writeln!(file, "  {}:", name)?;
+  writeln!(file, "    command: usr/bin/{}", bin_name)?;
+  writeln!(
+    file,
+    "    desktop: usr/share/applications/{}.desktop",
+    bin_name
+  )?;
+  writeln!(file, "    extensions: [gnome]")?;
+  if snap.confinement != SnapConfinement::Classic {
+    writeln!(file, "    plugs:")?;
+    for plug in list_or_default(&snap.plugs, DEFAULT_PLUGS) {
+      writeln!(file, "      - {}", plug)?;
+    }
+  }
+
+  writeln!(file, "\nparts:")?;
+  writeln!(file, "  {}:", name)?;
+  writeln!(file, "    plugin: dump")?;
+  // relative to the project directory, see `debian::generate_data`
+  writeln!(file, "    source: data")?;
+  writeln!(file, "    stage-packages:")?;
+  for package in list_or_default(&snap.stage_packages, DEFAULT_STAGE_PACKAGES) {
+    writeln!(file, "      - {}", package)?;
+  }
+
+  file.flush()?;
+  Ok(())
+}
+
+fn list_or_default<'a>(list: &'a Option<Vec<String>>, default: &[&'a str]) -> Vec<&'a str> {
+  match list {
+    Some(list) => list.iter().map(String::as_str).collect(),
+    None => default.to_vec(),
+  }
+}
+
+/// Quotes a string as a YAML double-quoted scalar.
+fn quote(value: &str) -> String {
+  serde_json::to_string(value).expect("failed to quote string")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::generate_snapcraft_file;
+  use crate::bundle::settings::{test_settings, BundleSettings, SnapConfinement, SnapSettings};
+  use std::fs;
+
+  #[test]
+  fn generates_snapcraft_file() {
+    let tmp = tempfile::tempdir().expect("unable to create temp dir");
+    let snapcraft_path = tmp.path().join("snap/snapcraft.yaml");
+
+    let settings = test_settings("x86_64-unknown-linux-gnu", Default::default());
+    generate_snapcraft_file(&settings, "tauri-app", &snapcraft_path).unwrap();
+    let snapcraft = fs::read_to_string(&snapcraft_path).unwrap();
+    assert!(snapcraft.starts_with(
+      "name: tauri-app\nbase: core22\nversion: \"1.2.3\"\nsummary: \"A Tauri App\"\ndescription: \"A Tauri App\"\ngrade: stable\nconfinement: strict\n"
+    ));
+    assert!(snapcraft.contains("    command: usr/bin/tauri-app\n"));
+    assert!(snapcraft.contains("    plugs:\n      - network\n"));
+    assert!(snapcraft.contains("    stage-packages:\n      - libwebkit2gtk-4.0-37\n"));
+
+    let settings = test_settings(
+      "x86_64-unknown-linux-gnu",
+      BundleSettings {
+        snap: SnapSettings {
+          confinement: SnapConfinement::Classic,
+          stage_packages: Some(vec!["libayatana-appindicator3-1".into()]),
+          ..Default::default()
+        },
+        ..Default::default()
+      },
+    );
+    generate_snapcraft_file(&settings, "tauri-app", &snapcraft_path).unwrap();
+    let snapcraft = fs::read_to_string(&snapcraft_path).unwrap();
+    assert!(snapcraft.contains("confinement: classic\n"));
+    // classic snaps don't use interfaces
+    assert!(!snapcraft.contains("plugs:"));
+    assert!(snapcraft.contains("    stage-packages:\n      - libayatana-appindicator3-1\n"));
+  }
+}
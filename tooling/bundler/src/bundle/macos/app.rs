@@ -72,6 +72,18 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
   create_info_plist(&bundle_directory, bundle_icon_file, settings)
     .with_context(|| "Failed to create Info.plist")?;
 
+  for icon in settings
+    .file_associations()
+    .iter()
+    .filter_map(|association| association.icon.as_ref())
+  {
+    common::copy_file(
+      icon,
+      &resources_dir.join(icon.file_name().expect("No file name")),
+    )
+    .with_context(|| format!("Failed to copy file association icon {}", icon.display()))?;
+  }
+
   copy_frameworks_to_bundle(&bundle_directory, settings)
     .with_context(|| "Failed to bundle frameworks")?;
 
@@ -87,7 +99,7 @@ pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
     // sign application
     sign(app_bundle_path.clone(), identity, settings, true)?;
     // notarization is required for distribution
-    match notarize_auth_args() {
+    match notarize_auth_args(settings) {
       Ok(args) => {
         notarize(app_bundle_path.clone(), args, settings)?;
       }
@@ -169,6 +181,80 @@ fn create_info_plist(
     plist.insert("NSHumanReadableCopyright".into(), copyright.into());
   }
 
+  if !settings.file_associations().is_empty() {
+    let document_types = settings
+      .file_associations()
+      .iter()
+      .map(|association| {
+        let mut document_type = plist::Dictionary::new();
+        document_type.insert(
+          "CFBundleTypeExtensions".into(),
+          plist::Value::Array(
+            association
+              .ext
+              .iter()
+              .map(|ext| ext.clone().into())
+              .collect(),
+          ),
+        );
+        document_type.insert(
+          "CFBundleTypeName".into(),
+          association
+            .name
+            .clone()
+            .or_else(|| association.ext.first().cloned())
+            .unwrap_or_default()
+            .into(),
+        );
+        document_type.insert("CFBundleTypeRole".into(), "Editor".into());
+        if let Some(mime_type) = &association.mime_type {
+          document_type.insert(
+            "CFBundleTypeMIMETypes".into(),
+            plist::Value::Array(vec![mime_type.clone().into()]),
+          );
+        }
+        if let Some(icon) = &association.icon {
+          document_type.insert(
+            "CFBundleTypeIconFile".into(),
+            icon
+              .file_name()
+              .expect("No file name")
+              .to_string_lossy()
+              .into_owned()
+              .into(),
+          );
+        }
+        plist::Value::Dictionary(document_type)
+      })
+      .collect();
+    plist.insert(
+      "CFBundleDocumentTypes".into(),
+      plist::Value::Array(document_types),
+    );
+  }
+
+  if !settings.deep_link_schemes().is_empty() {
+    let mut url_type = plist::Dictionary::new();
+    url_type.insert(
+      "CFBundleURLName".into(),
+      settings.bundle_identifier().into(),
+    );
+    url_type.insert(
+      "CFBundleURLSchemes".into(),
+      plist::Value::Array(
+        settings
+          .deep_link_schemes()
+          .iter()
+          .map(|scheme| scheme.clone().into())
+          .collect(),
+      ),
+    );
+    plist.insert(
+      "CFBundleURLTypes".into(),
+      plist::Value::Array(vec![url_type.into()]),
+    );
+  }
+
   if let Some(exception_domain) = settings.macos().exception_domain.clone() {
     let mut security = plist::Dictionary::new();
     let mut domain = plist::Dictionary::new();
@@ -3,15 +3,24 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use super::{app, icon::create_icns_file};
+use super::{
+  app,
+  icon::create_icns_file,
+  sign::{notarize, notarize_auth_args, sign},
+};
 use crate::{
-  bundle::{common::CommandExt, Bundle},
+  bundle::{
+    common::CommandExt,
+    settings::{Position, Size},
+    template::load_template,
+    Bundle,
+  },
   PackageType::MacOsBundle,
   Settings,
 };
 
 use anyhow::Context;
-use log::info;
+use log::{info, warn};
 
 use std::{
   env,
@@ -71,15 +80,25 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
   // write the scripts
   write(
     &bundle_script_path,
-    include_str!("templates/dmg/bundle_dmg"),
+    load_template("bundle_dmg", None, include_str!("templates/dmg/bundle_dmg"))?.as_ref(),
   )?;
   write(
     support_directory_path.join("template.applescript"),
-    include_str!("templates/dmg/template.applescript"),
+    load_template(
+      "template.applescript",
+      None,
+      include_str!("templates/dmg/template.applescript"),
+    )?
+    .as_ref(),
   )?;
   write(
     support_directory_path.join("eula-resources-template.xml"),
-    include_str!("templates/dmg/eula-resources-template.xml"),
+    load_template(
+      "eula-resources-template.xml",
+      None,
+      include_str!("templates/dmg/eula-resources-template.xml"),
+    )?
+    .as_ref(),
   )?;
 
   // chmod script for execution
@@ -92,46 +111,72 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
     .output()
     .expect("Failed to chmod script");
 
+  let dmg = settings.dmg();
+  let app_position = dmg.app_position.unwrap_or(Position { x: 180, y: 170 });
+  let application_folder_position = dmg
+    .application_folder_position
+    .unwrap_or(Position { x: 480, y: 170 });
+  let window_size = dmg.window_size.unwrap_or(Size {
+    width: 660,
+    height: 400,
+  });
+
   let mut args = vec![
-    "--volname",
-    product_name,
-    "--icon",
-    product_name,
-    "180",
-    "170",
-    "--app-drop-link",
-    "480",
-    "170",
-    "--window-size",
-    "660",
-    "400",
-    "--hide-extension",
-    &bundle_file_name,
+    "--volname".to_string(),
+    dmg
+      .volume_name
+      .clone()
+      .unwrap_or_else(|| product_name.to_string()),
+    "--icon".to_string(),
+    product_name.to_string(),
+    app_position.x.to_string(),
+    app_position.y.to_string(),
+    "--app-drop-link".to_string(),
+    application_folder_position.x.to_string(),
+    application_folder_position.y.to_string(),
+    "--window-size".to_string(),
+    window_size.width.to_string(),
+    window_size.height.to_string(),
+    "--hide-extension".to_string(),
+    bundle_file_name.clone(),
   ];
 
-  let icns_icon_path =
-    create_icns_file(&output_path, settings)?.map(|path| path.to_string_lossy().to_string());
-  if let Some(icon) = &icns_icon_path {
-    args.push("--volicon");
-    args.push(icon);
+  if let Some(window_position) = &dmg.window_position {
+    args.push("--window-pos".to_string());
+    args.push(window_position.x.to_string());
+    args.push(window_position.y.to_string());
+  }
+
+  if let Some(background) = &dmg.background {
+    args.push("--background".to_string());
+    args.push(
+      env::current_dir()?
+        .join(background)
+        .to_string_lossy()
+        .to_string(),
+    );
+  }
+
+  if let Some(icon) = create_icns_file(&output_path, settings)? {
+    args.push("--volicon".to_string());
+    args.push(icon.to_string_lossy().to_string());
   }
 
-  #[allow(unused_assignments)]
-  let mut license_path_ref = "".to_string();
   if let Some(license_path) = &settings.macos().license {
-    args.push("--eula");
-    license_path_ref = env::current_dir()?
-      .join(license_path)
-      .to_string_lossy()
-      .to_string();
-    args.push(&license_path_ref);
+    args.push("--eula".to_string());
+    args.push(
+      env::current_dir()?
+        .join(license_path)
+        .to_string_lossy()
+        .to_string(),
+    );
   }
 
   // Issue #592 - Building MacOS dmg files on CI
   // https://github.com/tauri-apps/tauri/issues/592
   if let Some(value) = env::var_os("CI") {
     if value == "true" {
-      args.push("--skip-jenkins");
+      args.push("--skip-jenkins".to_string());
     }
   }
 
@@ -149,7 +194,16 @@ pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<
 
   // Sign DMG if needed
   if let Some(identity) = &settings.macos().signing_identity {
-    super::sign::sign(dmg_path.clone(), identity, settings, false)?;
+    sign(dmg_path.clone(), identity, settings, false)?;
+    // the app inside is already notarized, but Gatekeeper also checks the disk image itself
+    match notarize_auth_args(settings) {
+      Ok(args) => {
+        notarize(dmg_path.clone(), args, settings)?;
+      }
+      Err(e) => {
+        warn!("skipping DMG notarization, {}", e.to_string());
+      }
+    }
   }
   Ok(vec![dmg_path])
 }
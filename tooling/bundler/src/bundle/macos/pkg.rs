@@ -0,0 +1,220 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The installer package is built in two steps:
+// `pkgbuild` creates a component package from the .app bundle (and the install scripts),
+// then `productbuild` wraps it in a product archive described by a generated distribution XML.
+
+use super::{
+  app,
+  sign::{notarize, notarize_auth_args},
+};
+use crate::{
+  bundle::{common, common::CommandExt, Bundle},
+  PackageType::MacOsBundle,
+  Settings,
+};
+
+use anyhow::Context;
+use log::{info, warn};
+
+use std::{
+  fs,
+  io::Write,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the installer package was created.
+pub fn bundle_project(settings: &Settings, bundles: &[Bundle]) -> crate::Result<Vec<PathBuf>> {
+  // generate the .app bundle if needed
+  let app_bundle_path = match bundles
+    .iter()
+    .find(|bundle| bundle.package_type == MacOsBundle)
+  {
+    Some(bundle) => bundle.bundle_paths[0].clone(),
+    None => app::bundle_project(settings)?.remove(0),
+  };
+
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    other => other,
+  };
+  let pkg_name = format!(
+    "{}_{}_{}.pkg",
+    settings.main_binary_name(),
+    settings.version_string(),
+    arch
+  );
+  let output_path = settings.project_out_directory().join("bundle/pkg");
+  let pkg_path = output_path.join(&pkg_name);
+  let work_dir = output_path.join("work");
+  if output_path.exists() {
+    fs::remove_dir_all(&output_path)
+      .with_context(|| format!("Failed to remove old {}", pkg_name))?;
+  }
+  fs::create_dir_all(&work_dir)?;
+
+  info!(action = "Bundling"; "{} ({})", pkg_name, pkg_path.display());
+
+  let pkg = settings.pkg();
+  let identifier = settings.bundle_identifier();
+  let component_name = format!("{}.pkg", settings.main_binary_name());
+
+  let mut pkgbuild = Command::new("pkgbuild");
+  pkgbuild
+    .arg("--component")
+    .arg(&app_bundle_path)
+    .arg("--install-location")
+    .arg(pkg.install_location.as_deref().unwrap_or("/Applications"))
+    .arg("--identifier")
+    .arg(identifier)
+    .arg("--version")
+    .arg(settings.version_string());
+  if pkg.pre_install_script.is_some() || pkg.post_install_script.is_some() {
+    let scripts_dir = work_dir.join("scripts");
+    copy_script(
+      pkg.pre_install_script.as_deref(),
+      &scripts_dir,
+      "preinstall",
+    )?;
+    copy_script(
+      pkg.post_install_script.as_deref(),
+      &scripts_dir,
+      "postinstall",
+    )?;
+    pkgbuild.arg("--scripts").arg(scripts_dir);
+  }
+  pkgbuild
+    .arg(work_dir.join(&component_name))
+    .output_ok()
+    .context("error running pkgbuild")?;
+
+  let distribution_path = work_dir.join("distribution.xml");
+  generate_distribution_file(settings, &component_name, &distribution_path)
+    .with_context(|| "Failed to create distribution file")?;
+
+  info!(action = "Running"; "productbuild to produce {}", pkg_path.display());
+
+  let mut productbuild = Command::new("productbuild");
+  productbuild
+    .arg("--distribution")
+    .arg(&distribution_path)
+    .arg("--package-path")
+    .arg(&work_dir);
+  if let Some(identity) = &pkg.signing_identity {
+    productbuild.arg("--sign").arg(identity);
+  }
+  productbuild
+    .arg(&pkg_path)
+    .output_ok()
+    .context("error running productbuild")?;
+
+  fs::remove_dir_all(&work_dir)?;
+
+  if pkg.signing_identity.is_some() {
+    // notarization is required for distribution
+    match notarize_auth_args(settings) {
+      Ok(args) => {
+        notarize(pkg_path.clone(), args, settings)?;
+      }
+      Err(e) => {
+        warn!("skipping installer package notarization, {}", e.to_string());
+      }
+    }
+  }
+
+  Ok(vec![pkg_path])
+}
+
+/// Copies an install script to the scripts directory with the name expected by `pkgbuild`.
+fn copy_script(script: Option<&Path>, scripts_dir: &Path, name: &str) -> crate::Result<()> {
+  if let Some(script) = script {
+    let dest = scripts_dir.join(name);
+    common::copy_file(script, &dest)
+      .with_context(|| format!("Failed to copy {} script {}", name, script.display()))?;
+    Command::new("chmod")
+      .arg("755")
+      .arg(&dest)
+      .output_ok()
+      .with_context(|| format!("Failed to make the {} script executable", name))?;
+  }
+  Ok(())
+}
+
+/// Generates the distribution XML describing the product archive.
+fn generate_distribution_file(
+  settings: &Settings,
+  component_name: &str,
+  distribution_path: &Path,
+) -> crate::Result<()> {
+  // For more information about the format of this file, see
+  // https://developer.apple.com/library/archive/documentation/DeveloperTools/Reference/DistributionDefinitionRef/Chapters/Distribution_XML_Ref.html
+  let identifier = settings.bundle_identifier();
+  let host_architectures = match settings.binary_arch() {
+    "x86_64" => "x86_64",
+    "aarch64" => "arm64",
+    _ => "x86_64,arm64",
+  };
+  let mut file = common::create_file(distribution_path)?;
+  writeln!(file, r#"<?xml version="1.0" encoding="utf-8"?>"#)?;
+  writeln!(file, r#"<installer-gui-script minSpecVersion="2">"#)?;
+  writeln!(file, "  <title>{}</title>", escape(settings.product_name()))?;
+  writeln!(
+    file,
+    r#"  <options customize="never" require-scripts="false" hostArchitectures="{}"/>"#,
+    host_architectures
+  )?;
+  writeln!(file, r#"  <domains enable_localSystem="true"/>"#)?;
+  writeln!(file, "  <choices-outline>")?;
+  writeln!(file, r#"    <line choice="default">"#)?;
+  writeln!(file, r#"      <line choice="{}"/>"#, identifier)?;
+  writeln!(file, "    </line>")?;
+  writeln!(file, "  </choices-outline>")?;
+  writeln!(file, r#"  <choice id="default"/>"#)?;
+  writeln!(file, r#"  <choice id="{}" visible="false">"#, identifier)?;
+  writeln!(file, r#"    <pkg-ref id="{}"/>"#, identifier)?;
+  writeln!(file, "  </choice>")?;
+  writeln!(
+    file,
+    r#"  <pkg-ref id="{}" version="{}" onConclusion="none">{}</pkg-ref>"#,
+    identifier,
+    escape(settings.version_string()),
+    escape(component_name)
+  )?;
+  writeln!(file, "</installer-gui-script>")?;
+  file.flush()?;
+  Ok(())
+}
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::generate_distribution_file;
+  use crate::bundle::settings::test_settings;
+  use std::fs;
+
+  #[test]
+  fn generates_distribution_file() {
+    let tmp = tempfile::tempdir().expect("unable to create temp dir");
+    let distribution_path = tmp.path().join("distribution.xml");
+    let settings = test_settings("aarch64-apple-darwin", Default::default());
+    generate_distribution_file(&settings, "tauri-app.pkg", &distribution_path).unwrap();
+    let distribution = fs::read_to_string(distribution_path).unwrap();
+
+    assert!(distribution.contains("  <title>Tauri App</title>\n"));
+    assert!(distribution.contains(r#"hostArchitectures="arm64""#));
+    assert!(distribution.contains(
+      r#"  <pkg-ref id="com.tauri.app" version="1.2.3" onConclusion="none">tauri-app.pkg</pkg-ref>"#
+    ));
+  }
+}
@@ -9,7 +9,7 @@ use std::{fs::File, io::prelude::*, path::PathBuf, process::Command};
 use crate::{bundle::common::CommandExt, Settings};
 use anyhow::Context;
 use log::info;
-use regex::Regex;
+use serde::Deserialize;
 
 const KEYCHAIN_ID: &str = "tauri-build.keychain";
 const KEYCHAIN_PWD: &str = "tauri-build";
@@ -212,168 +212,130 @@ fn try_sign(
   Ok(())
 }
 
-pub fn notarize(
-  app_bundle_path: PathBuf,
-  auth_args: Vec<String>,
-  settings: &Settings,
-) -> crate::Result<()> {
-  let identifier = settings.bundle_identifier();
+/// Submits the app bundle or disk image to Apple's notarization service,
+/// waits for the result and staples the ticket to it.
+pub fn notarize(path: PathBuf, auth_args: Vec<String>, settings: &Settings) -> crate::Result<()> {
+  let tmp_dir = tempfile::tempdir()?;
 
-  let bundle_stem = app_bundle_path
-    .file_stem()
-    .expect("failed to get bundle filename");
+  // the notary service only accepts zip archives, disk images and installer packages
+  let submission_path = if path.is_dir() {
+    let bundle_stem = path.file_stem().expect("failed to get bundle filename");
+    let zip_path = tmp_dir
+      .path()
+      .join(format!("{}.zip", bundle_stem.to_string_lossy()));
+    let zip_args = vec![
+      "-c",
+      "-k",
+      "--keepParent",
+      "--sequesterRsrc",
+      path
+        .to_str()
+        .expect("failed to convert bundle_path to string"),
+      zip_path
+        .to_str()
+        .expect("failed to convert zip_path to string"),
+    ];
 
-  let tmp_dir = tempfile::tempdir()?;
-  let zip_path = tmp_dir
-    .path()
-    .join(format!("{}.zip", bundle_stem.to_string_lossy()));
-  let zip_args = vec![
-    "-c",
-    "-k",
-    "--keepParent",
-    "--sequesterRsrc",
-    app_bundle_path
-      .to_str()
-      .expect("failed to convert bundle_path to string"),
-    zip_path
-      .to_str()
-      .expect("failed to convert zip_path to string"),
-  ];
-
-  // use ditto to create a PKZip almost identical to Finder
-  // this remove almost 99% of false alarm in notarization
-  Command::new("ditto")
-    .args(zip_args)
-    .output_ok()
-    .context("failed to zip app with ditto")?;
+    // use ditto to create a PKZip almost identical to Finder
+    // this remove almost 99% of false alarm in notarization
+    Command::new("ditto")
+      .args(zip_args)
+      .output_ok()
+      .context("failed to zip app with ditto")?;
 
-  // sign the zip file
-  if let Some(identity) = &settings.macos().signing_identity {
-    sign(zip_path.clone(), identity, settings, false)?;
-  };
+    // sign the zip file
+    if let Some(identity) = &settings.macos().signing_identity {
+      sign(zip_path.clone(), identity, settings, false)?;
+    };
 
-  let mut notarize_args = vec![
-    "altool",
-    "--notarize-app",
-    "-f",
     zip_path
-      .to_str()
-      .expect("failed to convert zip_path to string"),
-    "--primary-bundle-id",
-    identifier,
-  ];
-
-  if let Some(provider_short_name) = &settings.macos().provider_short_name {
-    notarize_args.push("--asc-provider");
-    notarize_args.push(provider_short_name);
-  }
+  } else {
+    path.clone()
+  };
+
+  info!(action = "Notarizing"; "{}", path.display());
 
-  info!(action = "Notarizing"; "{}", app_bundle_path.display());
+  // keep the app-specific password out of the logged command lines
+  let secrets: Vec<&str> = auth_args
+    .windows(2)
+    .filter(|pair| pair[0] == "--password")
+    .map(|pair| pair[1].as_str())
+    .collect();
 
+  // `--wait` polls the notary service until the submission is processed
   let output = Command::new("xcrun")
-    .args(notarize_args)
-    .args(auth_args.clone())
-    .output_ok()
+    .args(["notarytool", "submit"])
+    .arg(&submission_path)
+    .args(["--wait", "--output-format", "json"])
+    .args(&auth_args)
+    .output_ok_redacted(&secrets)
     .context("failed to upload app to Apple's notarization servers.")?;
 
-  // combine both stdout and stderr to support macOS below 10.15
-  let mut notarize_response = std::str::from_utf8(&output.stdout)?.to_string();
-  notarize_response.push('\n');
-  notarize_response.push_str(std::str::from_utf8(&output.stderr)?);
-  notarize_response.push('\n');
-  if let Some(uuid) = Regex::new(r"\nRequestUUID = (.+?)\n")?
-    .captures_iter(&notarize_response)
-    .next()
-  {
-    info!("notarization started; waiting for Apple response...");
-
-    let uuid = uuid[1].to_string();
-    get_notarization_status(uuid, auth_args, settings)?;
-    staple_app(app_bundle_path.clone())?;
+  let submission: NotarytoolSubmission = serde_json::from_slice(&output.stdout).map_err(|e| {
+    anyhow::anyhow!(
+      "failed to parse notarytool output: {}. {}",
+      e,
+      String::from_utf8_lossy(&output.stdout)
+    )
+  })?;
+
+  if submission.status == "Accepted" {
+    info!("notarization succeeded ({})", submission.id);
+    staple(path)?;
+    Ok(())
   } else {
-    return Err(
+    // the log explains why the submission was rejected
+    let log = Command::new("xcrun")
+      .args(["notarytool", "log", &submission.id])
+      .args(&auth_args)
+      .output_ok_redacted(&secrets)
+      .map(|output| String::from_utf8_lossy(&output.stdout).into_owned())
+      .unwrap_or_default();
+    Err(
       anyhow::anyhow!(
-        "failed to parse RequestUUID from upload output. {}",
-        notarize_response
+        "Apple failed to notarize your app, status: {} ({}). {}",
+        submission.status,
+        submission.message,
+        log
       )
       .into(),
-    );
+    )
   }
+}
 
-  Ok(())
+#[derive(Deserialize)]
+struct NotarytoolSubmission {
+  id: String,
+  status: String,
+  #[serde(default)]
+  message: String,
 }
 
-fn staple_app(mut app_bundle_path: PathBuf) -> crate::Result<()> {
-  let app_bundle_path_clone = app_bundle_path.clone();
-  let filename = app_bundle_path_clone
+fn staple(mut path: PathBuf) -> crate::Result<()> {
+  let path_clone = path.clone();
+  let filename = path_clone
     .file_name()
     .expect("failed to get bundle filename")
     .to_str()
     .expect("failed to convert bundle filename to string");
 
-  app_bundle_path.pop();
+  path.pop();
 
   Command::new("xcrun")
     .args(vec!["stapler", "staple", "-v", filename])
-    .current_dir(app_bundle_path)
+    .current_dir(path)
     .output_ok()
     .context("failed to staple app.")?;
 
   Ok(())
 }
 
-fn get_notarization_status(
-  uuid: String,
-  auth_args: Vec<String>,
-  settings: &Settings,
-) -> crate::Result<()> {
-  std::thread::sleep(std::time::Duration::from_secs(10));
-  let result = Command::new("xcrun")
-    .args(vec!["altool", "--notarization-info", &uuid])
-    .args(auth_args.clone())
-    .output_ok();
-
-  if let Ok(output) = result {
-    // combine both stdout and stderr to support macOS below 10.15
-    let mut notarize_status = std::str::from_utf8(&output.stdout)?.to_string();
-    notarize_status.push('\n');
-    notarize_status.push_str(std::str::from_utf8(&output.stderr)?);
-    notarize_status.push('\n');
-    if let Some(status) = Regex::new(r"\n *Status: (.+?)\n")?
-      .captures_iter(&notarize_status)
-      .next()
-    {
-      let status = status[1].to_string();
-      if status == "in progress" {
-        get_notarization_status(uuid, auth_args, settings)
-      } else if status == "invalid" {
-        Err(
-          anyhow::anyhow!(format!(
-            "Apple failed to notarize your app. {}",
-            notarize_status
-          ))
-          .into(),
-        )
-      } else if status != "success" {
-        Err(
-          anyhow::anyhow!(format!(
-            "Unknown notarize status {}. {}",
-            status, notarize_status
-          ))
-          .into(),
-        )
-      } else {
-        Ok(())
-      }
-    } else {
-      get_notarization_status(uuid, auth_args, settings)
-    }
-  } else {
-    get_notarization_status(uuid, auth_args, settings)
-  }
-}
-
-pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
+/// Returns the `notarytool` authentication arguments.
+///
+/// Either `APPLE_ID`, `APPLE_PASSWORD` (an app-specific password) and `APPLE_TEAM_ID`
+/// or the App Store Connect API key `APPLE_API_KEY`, `APPLE_API_ISSUER` and `APPLE_API_KEY_PATH` must be set.
+/// When `APPLE_TEAM_ID` is not set, the provider short name is used as the team ID.
+pub fn notarize_auth_args(settings: &Settings) -> crate::Result<Vec<String>> {
   match (
     std::env::var_os("APPLE_ID"),
     std::env::var_os("APPLE_PASSWORD"),
@@ -387,11 +349,25 @@ pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
         .to_str()
         .expect("failed to convert APPLE_PASSWORD to string")
         .to_string();
+      let team_id = match std::env::var("APPLE_TEAM_ID") {
+        Ok(team_id) => team_id,
+        Err(_) => settings
+          .macos()
+          .provider_short_name
+          .clone()
+          .ok_or_else(|| {
+            anyhow::anyhow!(
+              "the APPLE_TEAM_ID environment variable is required for notarization with APPLE_ID"
+            )
+          })?,
+      };
       Ok(vec![
-        "-u".to_string(),
+        "--apple-id".to_string(),
         apple_id,
-        "-p".to_string(),
+        "--password".to_string(),
         apple_password,
+        "--team-id".to_string(),
+        team_id,
       ])
     }
     _ => {
@@ -399,10 +375,46 @@ pub fn notarize_auth_args() -> crate::Result<Vec<String>> {
         (Some(api_key), Some(api_issuer)) => {
           let api_key = api_key.to_str().expect("failed to convert APPLE_API_KEY to string").to_string();
           let api_issuer = api_issuer.to_str().expect("failed to convert APPLE_API_ISSUER to string").to_string();
-          Ok(vec!["--apiKey".to_string(), api_key, "--apiIssuer".to_string(), api_issuer])
+          let key_path = find_api_key_path(&api_key)?;
+          Ok(vec![
+            "--key-id".to_string(),
+            api_key,
+            "--issuer".to_string(),
+            api_issuer,
+            "--key".to_string(),
+            key_path.to_string_lossy().into_owned(),
+          ])
         },
         _ => Err(anyhow::anyhow!("no APPLE_ID & APPLE_PASSWORD or APPLE_API_KEY & APPLE_API_ISSUER environment variables found").into())
       }
     }
   }
 }
+
+/// Finds the `AuthKey_<api_key>.p8` private key,
+/// using `APPLE_API_KEY_PATH` or the directories previously searched by `altool`.
+fn find_api_key_path(api_key: &str) -> crate::Result<PathBuf> {
+  if let Some(key_path) = std::env::var_os("APPLE_API_KEY_PATH") {
+    return Ok(PathBuf::from(key_path));
+  }
+
+  let file_name = format!("AuthKey_{}.p8", api_key);
+  let mut search_paths = vec![std::env::current_dir()?.join("private_keys")];
+  if let Some(home_dir) = dirs_next::home_dir() {
+    search_paths.push(home_dir.join("private_keys"));
+    search_paths.push(home_dir.join(".private_keys"));
+    search_paths.push(home_dir.join(".appstoreconnect/private_keys"));
+  }
+
+  search_paths
+    .into_iter()
+    .map(|dir| dir.join(&file_name))
+    .find(|path| path.exists())
+    .ok_or_else(|| {
+      anyhow::anyhow!(
+        "could not find {}, set the APPLE_API_KEY_PATH environment variable to its path",
+        file_name
+      )
+      .into()
+    })
+}
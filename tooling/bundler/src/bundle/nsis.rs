@@ -0,0 +1,411 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// Builds the NSIS installer with `makensis`, which is also available outside of Windows
+// (e.g. the `nsis` package of most Linux distributions), so Windows installers can be built on Linux CI.
+
+#[cfg(target_os = "windows")]
+use super::windows::{
+  sign::try_sign,
+  util::{download, WEBVIEW2_BOOTSTRAPPER_URL},
+};
+use super::{
+  common::CommandExt,
+  register_template_helpers,
+  settings::{Settings, WindowsInstallScope},
+  template::{insert_template_vars, load_template},
+};
+use anyhow::Context;
+use handlebars::{to_json, Handlebars};
+use log::info;
+use serde::Serialize;
+use std::{
+  collections::{BTreeMap, BTreeSet},
+  env::var_os,
+  fs::{create_dir_all, remove_dir_all, write},
+  path::{Path, PathBuf},
+  process::Command,
+};
+use tauri_utils::{config::WebviewInstallMode, resources::resource_relpath};
+
+pub const NSIS_OUTPUT_FOLDER_NAME: &str = "nsis";
+
+/// A binary to install next to the main executable.
+#[derive(Serialize)]
+struct Binary {
+  /// The path to the binary on the build machine.
+  path: PathBuf,
+  /// The file name of the installed binary.
+  name: String,
+}
+
+/// A resource file to install.
+#[derive(Serialize)]
+struct Resource {
+  /// The path to the resource on the build machine.
+  path: PathBuf,
+  /// The installed path, relative to the installation directory.
+  target: String,
+  /// The installed directory, relative to the installation directory.
+  dir: String,
+}
+
+/// Runs all of the commands to build the NSIS installer.
+/// Returns a vector of PathBuf that shows where the installer was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let makensis = locate_makensis()?;
+  build_nsis_app_installer(settings, &makensis)
+}
+
+fn build_nsis_app_installer(settings: &Settings, makensis: &Path) -> crate::Result<Vec<PathBuf>> {
+  if !settings.target().contains("windows") {
+    return Err(crate::Error::GenericError(format!(
+      "NSIS installers can only be built for Windows targets, but the target is {}",
+      settings.target()
+    )));
+  }
+
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target: {}",
+        target
+      )))
+    }
+  };
+
+  info!("Target: {}", arch);
+
+  let main_binary = settings
+    .binaries()
+    .iter()
+    .find(|bin| bin.main())
+    .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
+  let app_exe_source = settings.binary_path(main_binary);
+
+  let output_path = settings
+    .project_out_directory()
+    .join(NSIS_OUTPUT_FOLDER_NAME)
+    .join(arch);
+  if output_path.exists() {
+    remove_dir_all(&output_path)?;
+  }
+  create_dir_all(&output_path)?;
+
+  let installer_path = settings.project_out_directory().join(format!(
+    "bundle/{}/{}_{}_{}-setup.exe",
+    NSIS_OUTPUT_FOLDER_NAME,
+    settings.main_binary_name().replace(".exe", ""),
+    settings.version_string(),
+    arch,
+  ));
+  create_dir_all(installer_path.parent().unwrap())?;
+
+  let cwd = std::env::current_dir()?;
+  let mut data = BTreeMap::new();
+
+  let bundle_id = settings.bundle_identifier();
+  let manufacturer = settings
+    .publisher()
+    .unwrap_or_else(|| bundle_id.split('.').nth(1).unwrap_or(bundle_id));
+  data.insert("product_name", to_json(settings.product_name()));
+  data.insert("version", to_json(settings.version_string()));
+  data.insert("bundle_id", to_json(bundle_id));
+  data.insert("manufacturer", to_json(manufacturer));
+  data.insert("short_description", to_json(settings.short_description()));
+  data.insert("arch", to_json(arch));
+  data.insert(
+    "per_user_install",
+    to_json(settings.windows().install_scope == WindowsInstallScope::User),
+  );
+  data.insert(
+    "main_binary_name",
+    to_json(settings.main_binary_name().replace(".exe", "")),
+  );
+  data.insert("main_binary_path", to_json(&app_exe_source));
+  data.insert("out_file", to_json(&installer_path));
+
+  let binaries = generate_binaries_data(settings)?;
+  data.insert("binaries", to_json(&binaries));
+
+  let resources = generate_resource_data(settings)?;
+  let resource_dirs = resource_dirs(&resources);
+  data.insert("resources", to_json(&resources));
+  data.insert("resource_dirs", to_json(&resource_dirs));
+
+  match &settings.windows().webview_install_mode {
+    WebviewInstallMode::Skip => {}
+    WebviewInstallMode::FixedRuntime { path } => {
+      insert_fixed_runtime(&mut data, &cwd.join(path))?;
+    }
+    WebviewInstallMode::DownloadBootstrapper { silent }
+    | WebviewInstallMode::EmbedBootstrapper { silent }
+    | WebviewInstallMode::OfflineInstaller { silent } => {
+      if let Some(path) = &settings.windows().webview_fixed_runtime_path {
+        insert_fixed_runtime(&mut data, &cwd.join(path))?;
+      } else if let Some(webview2_bootstrapper_path) = download_webview2_bootstrapper(&output_path)?
+      {
+        data.insert(
+          "webview2_bootstrapper_path",
+          to_json(webview2_bootstrapper_path),
+        );
+        data.insert(
+          "webview_installer_args",
+          to_json(if *silent { "/silent" } else { "" }),
+        );
+      }
+    }
+  }
+
+  let installer_nsi_path = output_path.join("installer.nsi");
+  write(
+    &installer_nsi_path,
+    generate_installer_script(settings, data)?,
+  )?;
+
+  info!(action = "Running"; "makensis to produce {}", installer_path.display());
+
+  // makensis only accepts `-` prefixed options outside of Windows
+  Command::new(makensis)
+    .args(["-V3", "-INPUTCHARSET", "UTF8"])
+    .arg(&installer_nsi_path)
+    .current_dir(&output_path)
+    .output_ok()
+    .context("error running makensis")?;
+
+  #[cfg(target_os = "windows")]
+  try_sign(&installer_path, settings)?;
+
+  Ok(vec![installer_path])
+}
+
+/// Renders the `installer.nsi` script from the installer data.
+fn generate_installer_script<'a>(
+  settings: &'a Settings,
+  mut data: BTreeMap<&'a str, serde_json::Value>,
+) -> crate::Result<String> {
+  let cwd = std::env::current_dir()?;
+  let mut handlebars = Handlebars::new();
+  handlebars.register_escape_fn(handlebars::no_escape);
+  register_template_helpers(&mut handlebars);
+  if let Some(nsis) = &settings.windows().nsis {
+    if let Some(license) = &nsis.license {
+      data.insert("license", to_json(cwd.join(license)));
+    }
+    if let Some(header_image) = &nsis.header_image {
+      data.insert("header_image", to_json(cwd.join(header_image)));
+    }
+    if let Some(sidebar_image) = &nsis.sidebar_image {
+      data.insert("sidebar_image", to_json(cwd.join(sidebar_image)));
+    }
+    if let Some(installer_icon) = &nsis.installer_icon {
+      data.insert("installer_icon", to_json(cwd.join(installer_icon)));
+    }
+  }
+
+  let installer_nsi = load_template(
+    "installer.nsi",
+    settings
+      .windows()
+      .nsis
+      .as_ref()
+      .and_then(|nsis| nsis.template.as_deref()),
+    include_str!("windows/templates/installer.nsi"),
+  )?;
+  handlebars
+    .register_template_string("installer.nsi", installer_nsi)
+    .map_err(|e| e.to_string())
+    .expect("Failed to setup handlebar template");
+
+  insert_template_vars(settings, &mut data);
+  Ok(handlebars.render("installer.nsi", &data)?)
+}
+
+/// Lists the directories of the resources, removed deepest first when uninstalling.
+///
+/// The directories are Windows paths, so they are split on `\` regardless of the host.
+fn resource_dirs(resources: &[Resource]) -> Vec<String> {
+  resources
+    .iter()
+    .flat_map(|resource| {
+      let mut dirs = Vec::new();
+      let mut dir = resource.dir.as_str();
+      while !dir.is_empty() {
+        dirs.push(dir.to_string());
+        dir = dir.rfind('\\').map(|i| &dir[..i]).unwrap_or_default();
+      }
+      dirs
+    })
+    .collect::<BTreeSet<_>>()
+    .into_iter()
+    .rev()
+    .collect()
+}
+
+#[cfg(target_os = "windows")]
+fn download_webview2_bootstrapper(output_path: &Path) -> crate::Result<Option<PathBuf>> {
+  let webview2_bootstrapper_path = output_path.join("MicrosoftEdgeWebview2Setup.exe");
+  write(
+    &webview2_bootstrapper_path,
+    download(WEBVIEW2_BOOTSTRAPPER_URL)?,
+  )?;
+  Ok(Some(webview2_bootstrapper_path))
+}
+
+#[cfg(not(target_os = "windows"))]
+fn download_webview2_bootstrapper(_output_path: &Path) -> crate::Result<Option<PathBuf>> {
+  log::warn!("the Webview2 bootstrapper can only be embedded on Windows, make sure the Webview2 runtime is available on the target machines or use a fixed runtime");
+  Ok(None)
+}
+
+fn insert_fixed_runtime(
+  data: &mut BTreeMap<&str, serde_json::Value>,
+  path: &Path,
+) -> crate::Result<()> {
+  let dir_name = path
+    .file_name()
+    .ok_or_else(|| anyhow::anyhow!("invalid Webview2 fixed runtime path {}", path.display()))?;
+  data.insert("webview2_fixed_runtime_path", to_json(path));
+  data.insert(
+    "webview2_fixed_runtime_dir_name",
+    to_json(dir_name.to_string_lossy()),
+  );
+  Ok(())
+}
+
+/// Finds `makensis` in the default NSIS installation directories on Windows or on the `PATH`.
+fn locate_makensis() -> crate::Result<PathBuf> {
+  for program_files in [var_os("ProgramFiles(x86)"), var_os("ProgramFiles")]
+    .into_iter()
+    .flatten()
+  {
+    let makensis = PathBuf::from(program_files)
+      .join("NSIS")
+      .join("makensis.exe");
+    if makensis.exists() {
+      return Ok(makensis);
+    }
+  }
+
+  if Command::new("makensis").arg("-VERSION").output_ok().is_ok() {
+    return Ok(PathBuf::from("makensis"));
+  }
+
+  Err(crate::Error::GenericError(
+    "failed to find makensis, make sure NSIS (https://nsis.sourceforge.io) is installed and available on the PATH".into(),
+  ))
+}
+
+fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
+  let mut binaries = Vec::new();
+  let cwd = std::env::current_dir()?;
+
+  for src in settings.external_binaries() {
+    let src = src?;
+    let name = src
+      .file_name()
+      .expect("failed to extract external binary filename")
+      .to_string_lossy()
+      .replace(&format!("-{}", settings.target()), "");
+    binaries.push(Binary {
+      path: cwd.join(&src),
+      name,
+    });
+  }
+
+  for bin in settings.binaries() {
+    if !bin.main() {
+      let path = settings.binary_path(bin);
+      let name = path
+        .file_name()
+        .expect("failed to extract binary filename")
+        .to_string_lossy()
+        .into_owned();
+      binaries.push(Binary { path, name });
+    }
+  }
+
+  Ok(binaries)
+}
+
+fn generate_resource_data(settings: &Settings) -> crate::Result<Vec<Resource>> {
+  let mut resources = Vec::new();
+  let cwd = std::env::current_dir()?;
+
+  for src in settings.resource_files() {
+    let src = src?;
+    let target = resource_relpath(&src);
+    // the installed paths are Windows paths, even when the installer is built on another host
+    let dir = target
+      .parent()
+      .map(|dir| dir.to_string_lossy().replace('/', "\\"))
+      .unwrap_or_default();
+    resources.push(Resource {
+      path: cwd.join(&src),
+      target: target.to_string_lossy().replace('/', "\\"),
+      dir,
+    });
+  }
+
+  Ok(resources)
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{generate_installer_script, resource_dirs, Resource};
+  use crate::bundle::settings::test_settings;
+  use handlebars::to_json;
+  use std::{collections::BTreeMap, path::PathBuf};
+
+  fn resource(target: &str, dir: &str) -> Resource {
+    Resource {
+      path: PathBuf::from(target),
+      target: target.into(),
+      dir: dir.into(),
+    }
+  }
+
+  #[test]
+  fn orders_resource_dirs_deepest_first() {
+    let resources = vec![
+      resource("assets/logo.png", "assets"),
+      resource("assets/icons/app.ico", "assets\\icons"),
+      resource("README.md", ""),
+      resource("locales/en/app.ftl", "locales\\en"),
+    ];
+    assert_eq!(
+      resource_dirs(&resources),
+      vec!["locales\\en", "locales", "assets\\icons", "assets"]
+    );
+  }
+
+  #[test]
+  fn generates_installer_script() {
+    let settings = test_settings("x86_64-pc-windows-msvc", Default::default());
+
+    let mut data = BTreeMap::new();
+    data.insert("product_name", to_json(settings.product_name()));
+    data.insert("version", to_json(settings.version_string()));
+    data.insert("arch", to_json("x64"));
+    data.insert("per_user_install", to_json(true));
+    data.insert("main_binary_name", to_json("tauri-app"));
+    data.insert(
+      "resources",
+      to_json(vec![resource("C:\\app\\assets\\logo.png", "assets")]),
+    );
+    data.insert("resource_dirs", to_json(vec!["assets"]));
+
+    let script = generate_installer_script(&settings, data).unwrap();
+    assert!(script.contains("!define PRODUCTNAME \"Tauri App\"\n"));
+    assert!(script.contains("!define VERSION \"1.2.3\"\n"));
+    assert!(script.contains("RequestExecutionLevel user\n"));
+    assert!(script.contains("  SetOutPath \"$INSTDIR\\assets\"\n"));
+    assert!(script.contains("  File \"C:\\app\\assets\\logo.png\"\n"));
+    assert!(script.contains("  RMDir \"$INSTDIR\\assets\"\n"));
+    assert!(!script.contains("MicrosoftEdgeWebview2Setup.exe"));
+  }
+}
@@ -5,7 +5,10 @@
 
 use super::category::AppCategory;
 use crate::bundle::{common, platform::target_triple};
-pub use tauri_utils::config::WebviewInstallMode;
+pub use tauri_utils::config::{
+  FileAssociation, Position, ServiceConfig, Size, SnapConfinement, WebviewInstallMode,
+  WindowsInstallScope,
+};
 use tauri_utils::{
   config::BundleType,
   resources::{external_binaries, ResourcePaths},
@@ -26,14 +29,26 @@ pub enum PackageType {
   IosBundle,
   /// The Windows bundle (.msi).
   WindowsMsi,
+  /// The NSIS bundle (.exe).
+  Nsis,
+  /// The MSIX package (.msix).
+  Msix,
+  /// The Windows portable zip (.zip).
+  Portable,
   /// The Linux Debian package bundle (.deb).
   Deb,
   /// The Linux RPM bundle (.rpm).
   Rpm,
+  /// The Linux Flatpak bundle (.flatpak).
+  Flatpak,
+  /// The Linux Snap package (.snap).
+  Snap,
   /// The Linux AppImage bundle (.AppImage).
   AppImage,
   /// The macOS DMG bundle (.dmg).
   Dmg,
+  /// The macOS installer package (.pkg).
+  Pkg,
   /// The Updater bundle.
   Updater,
 }
@@ -42,10 +57,17 @@ impl From<BundleType> for PackageType {
   fn from(bundle: BundleType) -> Self {
     match bundle {
       BundleType::Deb => Self::Deb,
+      BundleType::Rpm => Self::Rpm,
+      BundleType::Flatpak => Self::Flatpak,
+      BundleType::Snap => Self::Snap,
       BundleType::AppImage => Self::AppImage,
       BundleType::Msi => Self::WindowsMsi,
+      BundleType::Nsis => Self::Nsis,
+      BundleType::Msix => Self::Msix,
+      BundleType::Portable => Self::Portable,
       BundleType::App => Self::MacOsBundle,
       BundleType::Dmg => Self::Dmg,
+      BundleType::Pkg => Self::Pkg,
       BundleType::Updater => Self::Updater,
     }
   }
@@ -53,17 +75,23 @@ impl From<BundleType> for PackageType {
 
 impl PackageType {
   /// Maps a short name to a PackageType.
-  /// Possible values are "deb", "ios", "msi", "app", "rpm", "appimage", "dmg", "updater".
+  /// Possible values are "deb", "ios", "msi", "nsis", "msix", "portable", "app", "rpm", "flatpak", "snap", "appimage", "dmg", "pkg", "updater".
   pub fn from_short_name(name: &str) -> Option<PackageType> {
     // Other types we may eventually want to support: apk.
     match name {
       "deb" => Some(PackageType::Deb),
       "ios" => Some(PackageType::IosBundle),
       "msi" => Some(PackageType::WindowsMsi),
+      "nsis" => Some(PackageType::Nsis),
+      "msix" => Some(PackageType::Msix),
+      "portable" => Some(PackageType::Portable),
       "app" => Some(PackageType::MacOsBundle),
       "rpm" => Some(PackageType::Rpm),
+      "flatpak" => Some(PackageType::Flatpak),
+      "snap" => Some(PackageType::Snap),
       "appimage" => Some(PackageType::AppImage),
       "dmg" => Some(PackageType::Dmg),
+      "pkg" => Some(PackageType::Pkg),
       "updater" => Some(PackageType::Updater),
       _ => None,
     }
@@ -76,10 +104,16 @@ impl PackageType {
       PackageType::Deb => "deb",
       PackageType::IosBundle => "ios",
       PackageType::WindowsMsi => "msi",
+      PackageType::Nsis => "nsis",
+      PackageType::Msix => "msix",
+      PackageType::Portable => "portable",
       PackageType::MacOsBundle => "app",
       PackageType::Rpm => "rpm",
+      PackageType::Flatpak => "flatpak",
+      PackageType::Snap => "snap",
       PackageType::AppImage => "appimage",
       PackageType::Dmg => "dmg",
+      PackageType::Pkg => "pkg",
       PackageType::Updater => "updater",
     }
   }
@@ -97,12 +131,24 @@ const ALL_PACKAGE_TYPES: &[PackageType] = &[
   PackageType::IosBundle,
   #[cfg(target_os = "windows")]
   PackageType::WindowsMsi,
+  #[cfg(target_os = "windows")]
+  PackageType::Nsis,
+  #[cfg(target_os = "windows")]
+  PackageType::Msix,
+  #[cfg(target_os = "windows")]
+  PackageType::Portable,
   #[cfg(target_os = "macos")]
   PackageType::MacOsBundle,
   #[cfg(target_os = "linux")]
   PackageType::Rpm,
+  #[cfg(target_os = "linux")]
+  PackageType::Flatpak,
+  #[cfg(target_os = "linux")]
+  PackageType::Snap,
   #[cfg(target_os = "macos")]
   PackageType::Dmg,
+  #[cfg(target_os = "macos")]
+  PackageType::Pkg,
   #[cfg(target_os = "linux")]
   PackageType::AppImage,
   PackageType::Updater,
@@ -149,6 +195,101 @@ pub struct DebianSettings {
   /// List of custom files to add to the deb package.
   /// Maps the path on the debian package to the path of the file to include (relative to the current working directory).
   pub files: HashMap<PathBuf, PathBuf>,
+  /// The package maintainer. Defaults to the package authors.
+  pub maintainer: Option<String>,
+  /// The application area the package belongs to.
+  pub section: Option<String>,
+  /// The package priority. Defaults to `optional`.
+  pub priority: Option<String>,
+}
+
+/// The RPM bundle settings.
+#[derive(Clone, Debug)]
+pub struct RpmSettings {
+  /// The package release number.
+  pub release: String,
+  /// The package license. Defaults to `Unspecified`.
+  pub license: Option<String>,
+  /// The list of RPM dependencies.
+  pub depends: Option<Vec<String>>,
+  /// Path to a shell script to run after the package is installed.
+  pub post_install_script: Option<PathBuf>,
+  /// Path to a shell script to run after the package is removed.
+  pub post_remove_script: Option<PathBuf>,
+}
+
+impl Default for RpmSettings {
+  fn default() -> Self {
+    Self {
+      release: "1".into(),
+      license: None,
+      depends: None,
+      post_install_script: None,
+      post_remove_script: None,
+    }
+  }
+}
+
+/// The Flatpak bundle settings.
+#[derive(Clone, Debug, Default)]
+pub struct FlatpakSettings {
+  /// The runtime the application runs on. Defaults to `org.gnome.Platform`.
+  pub runtime: Option<String>,
+  /// The runtime version. Defaults to `43`.
+  pub runtime_version: Option<String>,
+  /// The SDK used to build the application. Defaults to `org.gnome.Sdk`.
+  pub sdk: Option<String>,
+  /// The sandbox permissions granted to the application.
+  pub finish_args: Option<Vec<String>>,
+  /// The license of the project in SPDX format.
+  pub license: Option<String>,
+  /// Whether to run `flatpak-builder` to produce a `.flatpak` bundle.
+  pub build_bundle: bool,
+}
+
+/// The Snap package settings.
+#[derive(Clone, Debug, Default)]
+pub struct SnapSettings {
+  /// The confinement level of the package.
+  pub confinement: SnapConfinement,
+  /// The interfaces the app connects to.
+  pub plugs: Option<Vec<String>>,
+  /// The base snap. Defaults to `core22`.
+  pub base: Option<String>,
+  /// The list of Ubuntu packages staged in the snap.
+  pub stage_packages: Option<Vec<String>>,
+  /// Whether to run `snapcraft` to produce the `.snap` package.
+  pub build_snap: bool,
+}
+
+/// The DMG bundle settings.
+#[derive(Clone, Debug, Default)]
+pub struct DmgSettings {
+  /// The name of the mounted volume. Defaults to the main binary name.
+  pub volume_name: Option<String>,
+  /// Path to the image to use as the background of the DMG window.
+  pub background: Option<PathBuf>,
+  /// Position of the DMG window on the screen.
+  pub window_position: Option<Position>,
+  /// Size of the DMG window. Defaults to 660x400.
+  pub window_size: Option<Size>,
+  /// Position of the app icon in the DMG window. Defaults to (180, 170).
+  pub app_position: Option<Position>,
+  /// Position of the `/Applications` folder symlink in the DMG window. Defaults to (480, 170).
+  pub application_folder_position: Option<Position>,
+}
+
+/// The macOS installer package settings.
+#[derive(Clone, Debug, Default)]
+pub struct PkgSettings {
+  /// The directory where the app is installed. Defaults to `/Applications`.
+  pub install_location: Option<String>,
+  /// Path to a script to run before the app is installed.
+  pub pre_install_script: Option<PathBuf>,
+  /// Path to a script to run after the app is installed.
+  pub post_install_script: Option<PathBuf>,
+  /// The "Developer ID Installer" identity used to sign the package.
+  pub signing_identity: Option<String>,
 }
 
 /// The macOS bundle settings.
@@ -238,10 +379,56 @@ pub struct WixSettings {
 
   /// The required dimensions are 493px × 312px.
   pub dialog_image_path: Option<PathBuf>,
+  /// A GUID upgrade code for the MSI installer. Defaults to a UUID generated from the main binary name.
+  pub upgrade_code: Option<String>,
+  /// Embeds the Visual C++ redistributable in the installer and installs it when the target machine lacks the runtime.
+  pub bundle_vc_redist: bool,
+  /// The name of the Start Menu and desktop shortcuts. Defaults to the product name.
+  pub shortcut_name: Option<String>,
+  /// The name of the Start Menu folder containing the app shortcut. Defaults to the product name.
+  pub start_menu_folder: Option<String>,
+  /// Skips the creation of the desktop shortcut.
+  pub skip_desktop_shortcut: bool,
+  /// The default installation directory, using the Windows Installer folder properties.
+  pub install_dir: Option<String>,
   /// Enables FIPS compliant algorithms.
   pub fips_compliant: bool,
 }
 
+/// Settings specific to the NSIS implementation.
+#[derive(Clone, Debug, Default)]
+pub struct NsisSettings {
+  /// A custom .nsi template to use.
+  pub template: Option<PathBuf>,
+  /// The path to the license file to render on the installer.
+  pub license: Option<PathBuf>,
+  /// The path to a bitmap file to display on the header of installers pages.
+  ///
+  /// The recommended dimensions are 150px x 57px.
+  pub header_image: Option<PathBuf>,
+  /// The path to a bitmap file for the Welcome page and the Finish page.
+  ///
+  /// The recommended dimensions are 164px x 314px.
+  pub sidebar_image: Option<PathBuf>,
+  /// The path to an icon file used as the installer icon.
+  pub installer_icon: Option<PathBuf>,
+}
+
+/// Settings specific to the MSIX package.
+#[derive(Clone, Debug, Default)]
+pub struct MsixSettings {
+  /// The package identity name. Defaults to the bundle identifier.
+  pub identity_name: Option<String>,
+  /// The package publisher. Must match the subject of the signing certificate.
+  pub publisher: Option<String>,
+  /// The publisher name displayed to users.
+  pub publisher_display_name: Option<String>,
+  /// Additional capabilities to declare.
+  pub capabilities: Vec<String>,
+  /// A custom AppxManifest.xml template to use.
+  pub template: Option<PathBuf>,
+}
+
 /// The Windows bundle settings.
 #[derive(Clone, Debug)]
 pub struct WindowsSettings {
@@ -249,6 +436,10 @@ pub struct WindowsSettings {
   pub digest_algorithm: Option<String>,
   /// The SHA1 hash of the signing certificate.
   pub certificate_thumbprint: Option<String>,
+  /// Path to a PFX certificate file to sign with. Used when [`Self::certificate_thumbprint`] is not set.
+  pub certificate_path: Option<PathBuf>,
+  /// The password of the PFX certificate file.
+  pub certificate_password: Option<String>,
   /// Server to use during timestamping.
   pub timestamp_url: Option<String>,
   /// Whether to use Time-Stamp Protocol (TSP, a.k.a. RFC 3161) for the timestamp server. Your code signing provider may
@@ -256,6 +447,10 @@ pub struct WindowsSettings {
   pub tsp: bool,
   /// WiX configuration.
   pub wix: Option<WixSettings>,
+  /// NSIS configuration.
+  pub nsis: Option<NsisSettings>,
+  /// MSIX configuration.
+  pub msix: Option<MsixSettings>,
   /// The path to the application icon. Defaults to `./icons/icon.ico`.
   pub icon_path: PathBuf,
   /// The installation mode for the Webview2 runtime.
@@ -272,6 +467,8 @@ pub struct WindowsSettings {
   ///
   /// /// The default value of this flag is `true`.
   pub allow_downgrades: bool,
+  /// Whether the installer installs the app for the current user only or for all users of the machine.
+  pub install_scope: WindowsInstallScope,
 }
 
 impl Default for WindowsSettings {
@@ -279,13 +476,36 @@ impl Default for WindowsSettings {
     Self {
       digest_algorithm: None,
       certificate_thumbprint: None,
+      certificate_path: None,
+      certificate_password: None,
       timestamp_url: None,
       tsp: false,
       wix: None,
+      nsis: None,
+      msix: None,
       icon_path: PathBuf::from("icons/icon.ico"),
       webview_install_mode: Default::default(),
       webview_fixed_runtime_path: None,
       allow_downgrades: true,
+      install_scope: Default::default(),
+    }
+  }
+}
+
+/// The checksum files settings.
+#[derive(Clone, Debug)]
+pub struct ChecksumSettings {
+  /// Whether to write a `SHA256SUMS` file covering every release file.
+  pub sha256_sums: bool,
+  /// Whether to write a `<bundle>.sha256` file next to each hashed file.
+  pub individual: bool,
+}
+
+impl Default for ChecksumSettings {
+  fn default() -> Self {
+    Self {
+      sha256_sums: true,
+      individual: false,
     }
   }
 }
@@ -306,6 +526,8 @@ pub struct BundleSettings {
   ///
   /// supports glob patterns.
   pub resources: Option<Vec<String>>,
+  /// glob patterns of the resource files to leave out of the bundles.
+  pub exclude_resources: Option<Vec<String>>,
   /// the app's copyright.
   pub copyright: Option<String>,
   /// the app's category.
@@ -332,14 +554,36 @@ pub struct BundleSettings {
   /// e.g. `sqlite3-universal-apple-darwin`. See
   /// <https://developer.apple.com/documentation/apple-silicon/building-a-universal-macos-binary>
   pub external_bin: Option<Vec<String>>,
+  /// the file types the app is registered to open.
+  pub file_associations: Option<Vec<FileAssociation>>,
+  /// the custom URL schemes that launch the app.
+  pub deep_link_schemes: Vec<String>,
+  /// the service installed from the main binary.
+  pub service: Option<ServiceConfig>,
+  /// the GPG key used to sign the Linux packages.
+  pub gpg_key_id: Option<String>,
   /// Debian-specific settings.
   pub deb: DebianSettings,
+  /// RPM-specific settings.
+  pub rpm: RpmSettings,
+  /// Flatpak-specific settings.
+  pub flatpak: FlatpakSettings,
+  /// Snap-specific settings.
+  pub snap: SnapSettings,
+  /// DMG-specific settings.
+  pub dmg: DmgSettings,
+  /// Installer package specific settings.
+  pub pkg: PkgSettings,
   /// MacOS-specific settings.
   pub macos: MacOsSettings,
   /// Updater configuration.
   pub updater: Option<UpdaterSettings>,
   /// Windows-specific settings.
   pub windows: WindowsSettings,
+  /// Checksum files settings.
+  pub checksums: ChecksumSettings,
+  /// The variables added to the data of the installer templates.
+  pub template_vars: HashMap<String, serde_json::Value>,
 }
 
 /// A binary to bundle.
@@ -410,6 +654,24 @@ pub struct Settings {
   binaries: Vec<BundleBinary>,
   /// The target triple.
   target: String,
+  /// The tool used to build the MSI installer.
+  msi_backend: MsiBackend,
+}
+
+/// The tool used to build the Windows Installer (.msi) bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MsiBackend {
+  /// The WiX Toolset. Only available on Windows.
+  Wix,
+  /// `wixl` from msitools, which builds MSI installers on Linux and macOS.
+  /// Supports a subset of the WiX features: no installer UI, custom templates or WebView2 installation.
+  Wixl,
+}
+
+impl Default for MsiBackend {
+  fn default() -> Self {
+    Self::Wix
+  }
 }
 
 /// A builder for [`Settings`].
@@ -421,6 +683,7 @@ pub struct SettingsBuilder {
   bundle_settings: BundleSettings,
   binaries: Vec<BundleBinary>,
   target: Option<String>,
+  msi_backend: MsiBackend,
 }
 
 impl SettingsBuilder {
@@ -445,6 +708,13 @@ impl SettingsBuilder {
     self
   }
 
+  /// Sets the tool used to build the MSI installer.
+  #[must_use]
+  pub fn msi_backend(mut self, msi_backend: MsiBackend) -> Self {
+    self.msi_backend = msi_backend;
+    self
+  }
+
   /// Sets the package settings.
   #[must_use]
   pub fn package_settings(mut self, settings: PackageSettings) -> Self {
@@ -501,6 +771,7 @@ impl SettingsBuilder {
         ..self.bundle_settings
       },
       target,
+      msi_backend: self.msi_backend,
     })
   }
 }
@@ -528,8 +799,11 @@ impl Settings {
       "aarch64"
     } else if self.target.starts_with("universal") {
       "universal"
+    } else if self.target.starts_with("riscv64") {
+      "riscv64"
     } else {
-      panic!("Unexpected target triple {}", self.target)
+      // the bundlers that don't support the architecture report it as an error
+      self.target.split('-').next().unwrap_or(&self.target)
     }
   }
 
@@ -586,12 +860,28 @@ impl Settings {
     }
 
     if let Some(package_types) = &self.package_types {
+      // package types supported on this platform that are only bundled when requested
+      let mut opt_in_types: Vec<PackageType> = match target_os {
+        "linux" => vec![PackageType::Rpm, PackageType::Flatpak, PackageType::Snap],
+        "macos" => vec![PackageType::Pkg],
+        "windows" => vec![PackageType::Nsis, PackageType::Msix, PackageType::Portable],
+        _ => vec![],
+      };
+      if target_os != "windows" {
+        // makensis is cross-platform
+        opt_in_types.push(PackageType::Nsis);
+        // wixl can build MSI installers outside of Windows
+        if self.msi_backend == MsiBackend::Wixl {
+          opt_in_types.push(PackageType::WindowsMsi);
+        }
+      }
       let mut types = vec![];
       for package_type in package_types {
         let package_type = *package_type;
         if platform_types
           .clone()
           .into_iter()
+          .chain(opt_in_types.clone())
           .any(|t| t == package_type)
         {
           types.push(package_type);
@@ -603,6 +893,11 @@ impl Settings {
     }
   }
 
+  /// Returns the tool used to build the MSI installer.
+  pub fn msi_backend(&self) -> MsiBackend {
+    self.msi_backend
+  }
+
   /// Returns the product name.
   pub fn product_name(&self) -> &str {
     &self.package.product_name
@@ -628,10 +923,16 @@ impl Settings {
 
   /// Returns an iterator over the resource files to be included in this
   /// bundle.
+  ///
+  /// The files matching the `exclude_resources` patterns are skipped.
   pub fn resource_files(&self) -> ResourcePaths<'_> {
-    match self.bundle_settings.resources {
+    let paths = match self.bundle_settings.resources {
       Some(ref paths) => ResourcePaths::new(paths.as_slice(), true),
       None => ResourcePaths::new(&[], true),
+    };
+    match self.bundle_settings.exclude_resources {
+      Some(ref patterns) => paths.exclude(patterns),
+      None => paths,
     }
   }
 
@@ -703,6 +1004,38 @@ impl Settings {
     self.package.homepage.as_deref().unwrap_or("")
   }
 
+  /// Returns the file types the app is registered to open.
+  pub fn file_associations(&self) -> &[FileAssociation] {
+    self
+      .bundle_settings
+      .file_associations
+      .as_deref()
+      .unwrap_or_default()
+  }
+
+  /// Returns the custom URL schemes that launch the app.
+  pub fn deep_link_schemes(&self) -> &[String] {
+    &self.bundle_settings.deep_link_schemes
+  }
+
+  /// Returns the service installed from the main binary, if any.
+  pub fn service(&self) -> Option<&ServiceConfig> {
+    self.bundle_settings.service.as_ref()
+  }
+
+  /// Returns the name of the service installed from the main binary.
+  pub fn service_name(&self) -> &str {
+    self
+      .service()
+      .and_then(|service| service.name.as_deref())
+      .unwrap_or_else(|| self.main_binary_name())
+  }
+
+  /// Returns the GPG key used to sign the Linux packages, if any.
+  pub fn gpg_key_id(&self) -> Option<&str> {
+    self.bundle_settings.gpg_key_id.as_deref()
+  }
+
   /// Returns the app's category.
   pub fn app_category(&self) -> Option<AppCategory> {
     self.bundle_settings.category
@@ -727,6 +1060,31 @@ impl Settings {
     &self.bundle_settings.deb
   }
 
+  /// Returns the RPM settings.
+  pub fn rpm(&self) -> &RpmSettings {
+    &self.bundle_settings.rpm
+  }
+
+  /// Returns the Flatpak settings.
+  pub fn flatpak(&self) -> &FlatpakSettings {
+    &self.bundle_settings.flatpak
+  }
+
+  /// Returns the Snap settings.
+  pub fn snap(&self) -> &SnapSettings {
+    &self.bundle_settings.snap
+  }
+
+  /// Returns the DMG settings.
+  pub fn dmg(&self) -> &DmgSettings {
+    &self.bundle_settings.dmg
+  }
+
+  /// Returns the installer package settings.
+  pub fn pkg(&self) -> &PkgSettings {
+    &self.bundle_settings.pkg
+  }
+
   /// Returns the MacOS settings.
   pub fn macos(&self) -> &MacOsSettings {
     &self.bundle_settings.macos
@@ -737,6 +1095,16 @@ impl Settings {
     &self.bundle_settings.windows
   }
 
+  /// Returns the checksum files settings.
+  pub fn checksums(&self) -> &ChecksumSettings {
+    &self.bundle_settings.checksums
+  }
+
+  /// Returns the variables added to the data of the installer templates.
+  pub fn template_vars(&self) -> &HashMap<String, serde_json::Value> {
+    &self.bundle_settings.template_vars
+  }
+
   /// Returns the Updater settings.
   pub fn updater(&self) -> Option<&UpdaterSettings> {
     self.bundle_settings.updater.as_ref()
@@ -750,3 +1118,28 @@ impl Settings {
     }
   }
 }
+
+/// Builds the settings of a `tauri-app` binary for the given target, used by the bundler tests.
+#[cfg(test)]
+pub(crate) fn test_settings(target: &str, bundle_settings: BundleSettings) -> Settings {
+  SettingsBuilder::new()
+    .project_out_directory("target/release")
+    .package_settings(PackageSettings {
+      product_name: "Tauri App".into(),
+      version: "1.2.3".into(),
+      description: "A Tauri App".into(),
+      homepage: None,
+      authors: None,
+      default_run: None,
+    })
+    .bundle_settings(BundleSettings {
+      identifier: bundle_settings
+        .identifier
+        .or_else(|| Some("com.tauri.app".into())),
+      ..bundle_settings
+    })
+    .binaries(vec![BundleBinary::new("tauri-app".into(), true)])
+    .target(target.into())
+    .build()
+    .expect("failed to build the test settings")
+}
@@ -0,0 +1,108 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use anyhow::Context;
+use handlebars::{handlebars_helper, Handlebars};
+use heck::{ToKebabCase, ToLowerCamelCase, ToShoutySnakeCase, ToSnakeCase, ToUpperCamelCase};
+use log::{info, warn};
+
+use std::{
+  borrow::Cow,
+  fs::read_to_string,
+  path::{Path, PathBuf},
+};
+
+/// The folder of the Tauri project holding the files that override the built-in templates.
+const TEMPLATE_OVERRIDE_DIR: &str = "templates";
+
+handlebars_helper!(snake_case: |value: str| value.to_snake_case());
+handlebars_helper!(kebab_case: |value: str| value.to_kebab_case());
+handlebars_helper!(camel_case: |value: str| value.to_lower_camel_case());
+handlebars_helper!(pascal_case: |value: str| value.to_upper_camel_case());
+handlebars_helper!(upper_snake_case: |value: str| value.to_shouty_snake_case());
+handlebars_helper!(replace: |value: str, from: str, to: str| value.replace(from, to));
+
+/// Registers the string helpers available to the Tauri templates:
+///
+/// - `{{snake-case value}}`, `{{kebab-case value}}`, `{{camel-case value}}`,
+///   `{{pascal-case value}}` and `{{upper-snake-case value}}` convert the case of the value.
+/// - `{{replace value "from" "to"}}` replaces all occurrences of `from` with `to`.
+pub fn register_template_helpers(handlebars: &mut Handlebars<'_>) {
+  handlebars.register_helper("snake-case", Box::new(snake_case));
+  handlebars.register_helper("kebab-case", Box::new(kebab_case));
+  handlebars.register_helper("camel-case", Box::new(camel_case));
+  handlebars.register_helper("pascal-case", Box::new(pascal_case));
+  handlebars.register_helper("upper-snake-case", Box::new(upper_snake_case));
+  handlebars.register_helper("replace", Box::new(replace));
+}
+
+/// Adds the `tauri > bundle > template > vars` variables to the template data,
+/// keeping the values set by the bundler.
+pub(crate) fn insert_template_vars<'a>(
+  settings: &'a super::Settings,
+  data: &mut std::collections::BTreeMap<&'a str, serde_json::Value>,
+) {
+  for (name, value) in settings.template_vars() {
+    data.entry(name.as_str()).or_insert_with(|| value.clone());
+  }
+}
+
+/// Loads the template with the given file name, in order of precedence from:
+///
+/// 1. the template path set in the configuration;
+/// 2. the `templates` folder of the Tauri project, so individual files can be replaced;
+/// 3. the built-in template.
+pub(crate) fn load_template(
+  name: &str,
+  configured: Option<&Path>,
+  builtin: &'static str,
+) -> crate::Result<Cow<'static, str>> {
+  let project_template = PathBuf::from(TEMPLATE_OVERRIDE_DIR).join(name);
+  let path = match configured {
+    Some(path) => {
+      if project_template.is_file() {
+        warn!(
+          "ignoring {} since the configuration sets the {} template to {}",
+          project_template.display(),
+          name,
+          path.display()
+        );
+      }
+      path
+    }
+    None if project_template.is_file() => &project_template,
+    None => return Ok(Cow::Borrowed(builtin)),
+  };
+  info!(action = "Using"; "{} template from {}", name, path.display());
+  let template = read_to_string(path)
+    .with_context(|| format!("failed to read {} template {}", name, path.display()))?;
+  Ok(Cow::Owned(template))
+}
+
+#[cfg(test)]
+mod tests {
+  use super::register_template_helpers;
+  use handlebars::Handlebars;
+  use serde_json::json;
+
+  #[test]
+  fn renders_helpers() {
+    let mut handlebars = Handlebars::new();
+    register_template_helpers(&mut handlebars);
+    let data = json!({ "name": "my tauri-app" });
+    for (template, expected) in [
+      ("{{snake-case name}}", "my_tauri_app"),
+      ("{{kebab-case name}}", "my-tauri-app"),
+      ("{{camel-case name}}", "myTauriApp"),
+      ("{{pascal-case name}}", "MyTauriApp"),
+      ("{{upper-snake-case name}}", "MY_TAURI_APP"),
+      (r#"{{replace name "-" "."}}"#, "my tauri.app"),
+    ] {
+      assert_eq!(
+        handlebars.render_template(template, &data).unwrap(),
+        expected
+      );
+    }
+  }
+}
@@ -13,11 +13,11 @@ use super::linux::appimage;
 
 #[cfg(target_os = "windows")]
 use super::windows::msi;
+#[cfg(target_os = "windows")]
+use super::windows::util::zip_file_options;
 use log::error;
 #[cfg(target_os = "windows")]
 use std::{fs::File, io::prelude::*};
-#[cfg(target_os = "windows")]
-use zip::write::FileOptions;
 
 use crate::{bundle::Bundle, Settings};
 use anyhow::Context;
@@ -189,7 +189,7 @@ pub fn create_zip(src_file: &Path, dst_file: &Path) -> crate::Result<PathBuf> {
     .expect("Can't extract file name from path");
 
   let mut zip = zip::ZipWriter::new(writer);
-  let options = FileOptions::default()
+  let options = zip_file_options()
     .compression_method(zip::CompressionMethod::Stored)
     .unix_permissions(0o755);
 
@@ -206,7 +206,7 @@ pub fn create_zip(src_file: &Path, dst_file: &Path) -> crate::Result<PathBuf> {
 #[cfg(not(target_os = "windows"))]
 fn create_tar(src_dir: &Path, dest_path: &Path) -> crate::Result<PathBuf> {
   let dest_file = common::create_file(dest_path)?;
-  let gzip_encoder = libflate::gzip::Encoder::new(dest_file)?;
+  let gzip_encoder = common::gzip_encoder(dest_file)?;
 
   let gzip_encoder = create_tar_from_src(src_dir, gzip_encoder)?;
   let mut dest_file = gzip_encoder.finish().into_result()?;
@@ -223,14 +223,14 @@ fn create_tar_from_src<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> cr
   let file_type = fs::metadata(src_dir).expect("Can't read source directory");
   // if it's a file don't need to walkdir
   if file_type.is_file() {
-    let mut src_file = fs::File::open(src_dir)?;
     let file_name = src_dir
       .file_name()
       .expect("Can't extract file name from path");
 
-    tar_builder.append_file(file_name, &mut src_file)?;
+    common::append_to_tar(&mut tar_builder, Path::new(file_name), src_dir)?;
   } else {
-    for entry in walkdir::WalkDir::new(src_dir) {
+    // sorted to make the archive reproducible
+    for entry in walkdir::WalkDir::new(src_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
       let entry = entry?;
       let src_path = entry.path();
       if src_path == src_dir {
@@ -242,12 +242,7 @@ fn create_tar_from_src<P: AsRef<Path>, W: Write>(src_dir: P, dest_file: W) -> cr
       // We need a tar with app.app/<...> (source root folder should be included)
       // safe to unwrap: the path has a parent
       let dest_path = src_path.strip_prefix(src_dir.parent().unwrap())?;
-      if entry.file_type().is_dir() {
-        tar_builder.append_dir(dest_path, src_path)?;
-      } else {
-        let mut src_file = fs::File::open(src_path)?;
-        tar_builder.append_file(dest_path, &mut src_file)?;
-      }
+      common::append_to_tar(&mut tar_builder, dest_path, src_path)?;
     }
   }
   let dest_file = tar_builder.into_inner()?;
@@ -4,4 +4,7 @@
 // SPDX-License-Identifier: MIT
 
 pub mod msi;
+pub mod msix;
+pub mod portable;
 pub mod sign;
+pub mod util;
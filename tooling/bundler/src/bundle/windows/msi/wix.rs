@@ -3,18 +3,23 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use super::super::sign::{sign, SignParams};
+use super::super::{
+  sign::try_sign,
+  util::{download, download_and_verify, WEBVIEW2_BOOTSTRAPPER_URL},
+};
 use crate::bundle::{
   common::CommandExt,
   path_utils::{copy_file, FileOpts},
-  settings::Settings,
+  register_template_helpers,
+  settings::{Settings, WindowsInstallScope},
+  template::{insert_template_vars, load_template},
+  wix_guid::{generate_component_guid, generate_guid, generate_package_guid, upgrade_code},
 };
 use anyhow::{bail, Context};
 use handlebars::{to_json, Handlebars};
-use log::info;
+use log::{info, warn};
 use regex::Regex;
 use serde::{Deserialize, Serialize};
-use sha2::Digest;
 use std::{
   collections::{BTreeMap, HashMap},
   fs::{create_dir_all, read_to_string, remove_dir_all, rename, write, File},
@@ -23,7 +28,6 @@ use std::{
   process::Command,
 };
 use tauri_utils::{config::WebviewInstallMode, resources::resource_relpath};
-use uuid::Uuid;
 use zip::ZipArchive;
 
 // URLS for the WIX toolchain.  Can be used for cross-platform compilation.
@@ -32,29 +36,22 @@ pub const WIX_URL: &str =
 pub const WIX_SHA256: &str = "2c1888d5d1dba377fc7fa14444cf556963747ff9a0a289a3599cf09da03b9e2e";
 pub const MSI_FOLDER_NAME: &str = "msi";
 pub const MSI_UPDATER_FOLDER_NAME: &str = "msi-updater";
-const WEBVIEW2_BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
 const WEBVIEW2_X86_INSTALLER_GUID: &str = "a17bde80-b5ab-47b5-8bbb-1cbe93fc6ec9";
 const WEBVIEW2_X64_INSTALLER_GUID: &str = "aa5fd9b3-dc11-4cbc-8343-a50f57b311e1";
 
-// For Cross Platform Compilation.
-
-// const VC_REDIST_X86_URL: &str =
-//     "https://download.visualstudio.microsoft.com/download/pr/c8edbb87-c7ec-4500-a461-71e8912d25e9/99ba493d660597490cbb8b3211d2cae4/vc_redist.x86.exe";
-
-// const VC_REDIST_X86_SHA256: &str =
-//   "3a43e8a55a3f3e4b73d01872c16d47a19dd825756784f4580187309e7d1fcb74";
-
-// const VC_REDIST_X64_URL: &str =
-//     "https://download.visualstudio.microsoft.com/download/pr/9e04d214-5a9d-4515-9960-3d71398d98c3/1e1e62ab57bbb4bf5199e8ce88f040be/vc_redist.x64.exe";
+const VC_REDIST_X86_URL: &str =
+  "https://download.visualstudio.microsoft.com/download/pr/c8edbb87-c7ec-4500-a461-71e8912d25e9/99ba493d660597490cbb8b3211d2cae4/vc_redist.x86.exe";
+const VC_REDIST_X86_SHA256: &str =
+  "3a43e8a55a3f3e4b73d01872c16d47a19dd825756784f4580187309e7d1fcb74";
+const VC_REDIST_X64_URL: &str =
+  "https://download.visualstudio.microsoft.com/download/pr/9e04d214-5a9d-4515-9960-3d71398d98c3/1e1e62ab57bbb4bf5199e8ce88f040be/vc_redist.x64.exe";
+const VC_REDIST_X64_SHA256: &str =
+  "d6cd2445f68815fe02489fafe0127819e44851e26dfbe702612bc0d223cbbc2b";
 
-// const VC_REDIST_X64_SHA256: &str =
-//   "d6cd2445f68815fe02489fafe0127819e44851e26dfbe702612bc0d223cbbc2b";
-
-// A v4 UUID that was generated specifically for tauri-bundler, to be used as a
-// namespace for generating v5 UUIDs from bundle identifier strings.
-const UUID_NAMESPACE: [u8; 16] = [
-  0xfd, 0x85, 0x95, 0xa8, 0x17, 0xa3, 0x47, 0x4e, 0xa6, 0x16, 0x76, 0x14, 0x8d, 0xfa, 0x0c, 0x7b,
-];
+/// The size of the installer UI banner, in pixels.
+const BANNER_SIZE: (u32, u32) = (493, 58);
+/// The size of the installer UI welcome and completion dialog image, in pixels.
+const DIALOG_IMAGE_SIZE: (u32, u32) = (493, 312);
 
 /// Mapper between a resource directory name and its ResourceDirectory descriptor.
 type ResourceMap = BTreeMap<String, ResourceDirectory>;
@@ -106,6 +103,30 @@ struct ResourceDirectory {
   directories: Vec<ResourceDirectory>,
 }
 
+/// A file type registered in the WIX XML.
+#[derive(Serialize)]
+struct FileAssociationData {
+  /// the ProgId of the file type.
+  prog_id: String,
+  /// the description of the file type.
+  description: String,
+  /// the id of the file association icon to use on the WIX XML.
+  icon_id: Option<String>,
+  /// the file association icon path.
+  icon_path: Option<String>,
+  /// the file extensions of the file type.
+  extensions: Vec<FileAssociationExtension>,
+}
+
+/// A file extension registered in the WIX XML.
+#[derive(Serialize)]
+struct FileAssociationExtension {
+  /// the extension, without the leading dot.
+  ext: String,
+  /// the MIME type of the extension.
+  mime_type: Option<String>,
+}
+
 impl ResourceDirectory {
   /// Adds a file to this directory descriptor.
   fn add_file(&mut self, file: ResourceFile) {
@@ -140,7 +161,7 @@ impl ResourceDirectory {
     } else {
       format!(
         r#"<Directory Id="I{id}" Name="{name}">{files}{directories}</Directory>"#,
-        id = Uuid::new_v4().as_simple(),
+        id = generate_guid(self.path.as_bytes()).as_simple(),
         name = self.name,
         files = files,
         directories = directories,
@@ -174,30 +195,6 @@ fn copy_icon(settings: &Settings, filename: &str, path: &Path) -> crate::Result<
   Ok(icon_target_path)
 }
 
-fn download(url: &str) -> crate::Result<Vec<u8>> {
-  info!(action = "Downloading"; "{}", url);
-  let response = attohttpc::get(url).send()?;
-  response.bytes().map_err(Into::into)
-}
-
-/// Function used to download Wix. Checks SHA256 to verify the download.
-fn download_and_verify(url: &str, hash: &str) -> crate::Result<Vec<u8>> {
-  let data = download(url)?;
-  info!("validating hash");
-
-  let mut hasher = sha2::Sha256::new();
-  hasher.update(&data);
-
-  let url_hash = hasher.finalize().to_vec();
-  let expected_hash = hex::decode(hash)?;
-
-  if expected_hash == url_hash {
-    Ok(data)
-  } else {
-    Err(crate::Error::HashError)
-  }
-}
-
 /// The app installer output path.
 fn app_installer_output_path(
   settings: &Settings,
@@ -259,15 +256,37 @@ fn extract_zip(data: &[u8], path: &Path) -> crate::Result<()> {
   Ok(())
 }
 
-/// Generates the UUID for the Wix template.
-fn generate_package_guid(settings: &Settings) -> Uuid {
-  generate_guid(settings.bundle_identifier().as_bytes())
-}
-
-/// Generates a GUID.
-fn generate_guid(key: &[u8]) -> Uuid {
-  let namespace = Uuid::from_bytes(UUID_NAMESPACE);
-  Uuid::new_v5(&namespace, key)
+/// Checks the format and size of an installer UI bitmap, since WiX only reads BMP and PNG images
+/// and stretches images with other dimensions.
+fn check_image_dimensions(path: &Path, name: &str, expected: (u32, u32)) -> crate::Result<()> {
+  let reader = image::io::Reader::open(path)
+    .and_then(|reader| reader.with_guessed_format())
+    .with_context(|| format!("Failed to read the WiX {} {}", name, path.display()))?;
+  if !matches!(
+    reader.format(),
+    Some(image::ImageFormat::Bmp) | Some(image::ImageFormat::Png)
+  ) {
+    bail!(
+      "the WiX {} {} must be a BMP or PNG image",
+      name,
+      path.display()
+    );
+  }
+  let dimensions = reader
+    .into_dimensions()
+    .with_context(|| format!("Failed to read the WiX {} {}", name, path.display()))?;
+  if dimensions != expected {
+    bail!(
+      "the WiX {} {} is {}x{} pixels, but it must be {}x{} pixels",
+      name,
+      path.display(),
+      dimensions.0,
+      dimensions.1,
+      expected.0,
+      expected.1
+    );
+  }
+  Ok(())
 }
 
 // Specifically goes and gets Wix and verifies the download via Sha256
@@ -299,6 +318,7 @@ fn run_candle(
   wix_toolset_path: &Path,
   cwd: &Path,
   wxs_file_path: PathBuf,
+  output_file_name: &str,
   extensions: Vec<PathBuf>,
 ) -> crate::Result<()> {
   let arch = match settings.binary_arch() {
@@ -322,6 +342,8 @@ fn run_candle(
     "-arch".to_string(),
     arch.to_string(),
     wxs_file_path.to_string_lossy().to_string(),
+    "-out".to_string(),
+    output_file_name.to_string(),
     format!(
       "-dSourceDir={}",
       settings.binary_path(main_binary).display()
@@ -347,11 +369,8 @@ fn run_candle(
     cmd.arg(ext);
   }
   clear_env_for_wix(&mut cmd);
-  cmd
-    .args(&args)
-    .current_dir(cwd)
-    .output_ok()
-    .context("error running candle.exe")?;
+  cmd.args(&args).current_dir(cwd);
+  run_wix_tool(&mut cmd, "candle.exe")?;
 
   Ok(())
 }
@@ -383,15 +402,40 @@ fn run_light(
     cmd.arg(ext);
   }
   clear_env_for_wix(&mut cmd);
-  cmd
-    .args(&args)
-    .current_dir(build_path)
-    .output_ok()
-    .context("error running light.exe")?;
+  cmd.args(&args).current_dir(build_path);
+  run_wix_tool(&mut cmd, "light.exe")?;
 
   Ok(())
 }
 
+/// Runs a WiX tool, returning the errors it printed if it fails.
+fn run_wix_tool(cmd: &mut Command, tool: &'static str) -> crate::Result<()> {
+  match cmd.output_ok() {
+    Ok(_) => Ok(()),
+    Err(crate::Error::CommandFailed { stdout, stderr, .. }) => {
+      // the WiX tools print their diagnostics to stdout,
+      // e.g. `main.wxs(12) : error CNDL0104 : Not a valid source file`
+      let mut diagnostics = stdout
+        .lines()
+        .chain(stderr.lines())
+        .filter(|line| line.contains(" error "))
+        .map(|line| line.trim().to_string())
+        .collect::<Vec<_>>();
+      if diagnostics.is_empty() {
+        diagnostics = stderr
+          .lines()
+          .chain(stdout.lines())
+          .map(str::trim)
+          .filter(|line| !line.is_empty())
+          .map(ToString::to_string)
+          .collect();
+      }
+      Err(crate::Error::WixError { tool, diagnostics })
+    }
+    Err(e) => Err(e),
+  }
+}
+
 // fn get_icon_data() -> crate::Result<()> {
 //   Ok(())
 // }
@@ -442,33 +486,6 @@ pub fn build_wix_app_installer(
     .find(|bin| bin.main())
     .ok_or_else(|| anyhow::anyhow!("Failed to get main binary"))?;
   let app_exe_source = settings.binary_path(main_binary);
-  let try_sign = |file_path: &PathBuf| -> crate::Result<()> {
-    if let Some(certificate_thumbprint) = &settings.windows().certificate_thumbprint {
-      info!(action = "Signing"; "{}", file_path.display());
-      sign(
-        &file_path,
-        &SignParams {
-          product_name: settings.product_name().into(),
-          digest_algorithm: settings
-            .windows()
-            .digest_algorithm
-            .as_ref()
-            .map(|algorithm| algorithm.to_string())
-            .unwrap_or_else(|| "sha256".to_string()),
-          certificate_thumbprint: certificate_thumbprint.to_string(),
-          timestamp_url: settings
-            .windows()
-            .timestamp_url
-            .as_ref()
-            .map(|url| url.to_string()),
-          tsp: settings.windows().tsp,
-        },
-      )?;
-    }
-    Ok(())
-  };
-
-  try_sign(&app_exe_source)?;
 
   let output_path = settings.project_out_directory().join("wix").join(arch);
 
@@ -571,6 +588,31 @@ pub fn build_wix_app_installer(
     }
   }
 
+  if settings
+    .windows()
+    .wix
+    .as_ref()
+    .map(|w| w.bundle_vc_redist)
+    .unwrap_or_default()
+  {
+    let (url, hash) = if arch == "x64" {
+      (VC_REDIST_X64_URL, VC_REDIST_X64_SHA256)
+    } else {
+      (VC_REDIST_X86_URL, VC_REDIST_X86_SHA256)
+    };
+    let mut vc_redist_path =
+      dirs_next::cache_dir().ok_or_else(|| anyhow::anyhow!("failed to get the cache directory"))?;
+    vc_redist_path.push("tauri");
+    vc_redist_path.push("VCRedist");
+    vc_redist_path.push(arch);
+    create_dir_all(&vc_redist_path)?;
+    let vc_redist_path = vc_redist_path.join(format!("vc_redist.{}.exe", arch));
+    if !vc_redist_path.exists() {
+      std::fs::write(&vc_redist_path, download_and_verify(url, hash)?)?;
+    }
+    data.insert("vc_redist_path", to_json(vc_redist_path));
+  }
+
   let language_map: HashMap<String, LanguageMetadata> =
     serde_json::from_str(include_str!("./languages.json")).unwrap();
 
@@ -613,17 +655,39 @@ pub fn build_wix_app_installer(
     .unwrap_or_else(|| bundle_id.split('.').nth(1).unwrap_or(bundle_id));
   data.insert("bundle_id", to_json(bundle_id));
   data.insert("manufacturer", to_json(manufacturer));
-  let upgrade_code = Uuid::new_v5(
-    &Uuid::NAMESPACE_DNS,
-    format!("{}.app.x64", &settings.main_binary_name()).as_bytes(),
-  )
-  .to_string();
-
+  let upgrade_code = upgrade_code(settings)?.to_string();
   data.insert("upgrade_code", to_json(&upgrade_code.as_str()));
   data.insert(
     "allow_downgrades",
     to_json(settings.windows().allow_downgrades),
   );
+  let per_user_install = settings.windows().install_scope == WindowsInstallScope::User;
+  data.insert("per_user_install", to_json(per_user_install));
+
+  let wix = settings.windows().wix.as_ref();
+  data.insert(
+    "shortcut_name",
+    to_json(
+      wix
+        .and_then(|wix| wix.shortcut_name.as_deref())
+        .unwrap_or_else(|| settings.product_name()),
+    ),
+  );
+  data.insert(
+    "start_menu_folder",
+    to_json(
+      wix
+        .and_then(|wix| wix.start_menu_folder.as_deref())
+        .unwrap_or_else(|| settings.product_name()),
+    ),
+  );
+  data.insert(
+    "skip_desktop_shortcut",
+    to_json(wix.map(|wix| wix.skip_desktop_shortcut).unwrap_or_default()),
+  );
+  if let Some(install_dir) = wix.and_then(|wix| wix.install_dir.as_ref()) {
+    data.insert("install_dir", to_json(install_dir));
+  }
 
   let path_guid = generate_package_guid(settings).to_string();
   data.insert("path_component_guid", to_json(&path_guid.as_str()));
@@ -639,6 +703,29 @@ pub fn build_wix_app_installer(
   let binaries_json = to_json(&binaries);
   data.insert("binaries", binaries_json);
 
+  data.insert("deep_link_schemes", to_json(settings.deep_link_schemes()));
+  if let Some(service) = settings.service() {
+    if per_user_install {
+      warn!("Windows services can only be installed by per-machine installers, set `installScope` to `machine`");
+    }
+    data.insert(
+      "service",
+      to_json(serde_json::json!({
+        "name": settings.service_name(),
+        "display_name": settings.product_name(),
+        "description": service
+          .description
+          .as_deref()
+          .unwrap_or_else(|| settings.short_description()),
+        "args": service.args.join(" "),
+      })),
+    );
+  }
+  data.insert(
+    "file_associations",
+    to_json(generate_file_associations_data(settings)?),
+  );
+
   let resources = generate_resource_data(settings)?;
   let mut resources_wix_string = String::from("");
   let mut files_ids = Vec::new();
@@ -665,7 +752,7 @@ pub fn build_wix_app_installer(
 
   let mut fragment_paths = Vec::new();
   let mut handlebars = Handlebars::new();
-  let mut has_custom_template = false;
+  register_template_helpers(&mut handlebars);
   let mut enable_elevated_update_task = false;
 
   if let Some(wix) = &settings.windows().wix {
@@ -677,16 +764,8 @@ pub fn build_wix_app_installer(
     fragment_paths = wix.fragment_paths.clone();
     enable_elevated_update_task = wix.enable_elevated_update_task;
 
-    if let Some(temp_path) = &wix.template {
-      let template = read_to_string(temp_path)?;
-      handlebars
-        .register_template_string("main.wxs", &template)
-        .map_err(|e| e.to_string())
-        .expect("Failed to setup custom handlebar template");
-      has_custom_template = true;
-    }
-
     if let Some(banner_path) = &wix.banner_path {
+      check_image_dimensions(banner_path, "banner", BANNER_SIZE)?;
       let filename = banner_path
         .file_name()
         .unwrap()
@@ -699,6 +778,7 @@ pub fn build_wix_app_installer(
     }
 
     if let Some(dialog_image_path) = &wix.dialog_image_path {
+      check_image_dimensions(dialog_image_path, "dialog image", DIALOG_IMAGE_SIZE)?;
       let filename = dialog_image_path
         .file_name()
         .unwrap()
@@ -711,12 +791,21 @@ pub fn build_wix_app_installer(
     }
   }
 
-  if !has_custom_template {
-    handlebars
-      .register_template_string("main.wxs", include_str!("../templates/main.wxs"))
-      .map_err(|e| e.to_string())
-      .expect("Failed to setup handlebar template");
-  }
+  let main_wxs = load_template(
+    "main.wxs",
+    settings
+      .windows()
+      .wix
+      .as_ref()
+      .and_then(|wix| wix.template.as_deref()),
+    include_str!("../templates/main.wxs"),
+  )?;
+  handlebars
+    .register_template_string("main.wxs", main_wxs)
+    .map_err(|e| e.to_string())
+    .expect("Failed to setup handlebar template");
+
+  insert_template_vars(settings, &mut data);
 
   if enable_elevated_update_task {
     data.insert(
@@ -732,7 +821,12 @@ pub fn build_wix_app_installer(
 
     // Create the update task XML
     let mut skip_uac_task = Handlebars::new();
-    let xml = include_str!("../templates/update-task.xml");
+    register_template_helpers(&mut skip_uac_task);
+    let xml = load_template(
+      "update-task.xml",
+      None,
+      include_str!("../templates/update-task.xml"),
+    )?;
     skip_uac_task
       .register_template_string("update.xml", xml)
       .map_err(|e| e.to_string())
@@ -743,7 +837,12 @@ pub fn build_wix_app_installer(
 
     // Create the Powershell script to install the task
     let mut skip_uac_task_installer = Handlebars::new();
-    let xml = include_str!("../templates/install-task.ps1");
+    register_template_helpers(&mut skip_uac_task_installer);
+    let xml = load_template(
+      "install-task.ps1",
+      None,
+      include_str!("../templates/install-task.ps1"),
+    )?;
     skip_uac_task_installer
       .register_template_string("install-task.ps1", xml)
       .map_err(|e| e.to_string())
@@ -754,7 +853,12 @@ pub fn build_wix_app_installer(
 
     // Create the Powershell script to uninstall the task
     let mut skip_uac_task_uninstaller = Handlebars::new();
-    let xml = include_str!("../templates/uninstall-task.ps1");
+    register_template_helpers(&mut skip_uac_task_uninstaller);
+    let xml = load_template(
+      "uninstall-task.ps1",
+      None,
+      include_str!("../templates/uninstall-task.ps1"),
+    )?;
     skip_uac_task_uninstaller
       .register_template_string("uninstall-task.ps1", xml)
       .map_err(|e| e.to_string())
@@ -769,40 +873,56 @@ pub fn build_wix_app_installer(
   let main_wxs_path = output_path.join("main.wxs");
   write(&main_wxs_path, handlebars.render("main.wxs", &data)?)?;
 
-  let mut candle_inputs = vec![("main.wxs".into(), Vec::new())];
+  let mut candle_inputs = vec![("main.wxs".into(), "main.wixobj".to_string(), Vec::new())];
 
   let current_dir = std::env::current_dir()?;
   let extension_regex = Regex::new("\"http://schemas.microsoft.com/wix/(\\w+)\"")?;
-  for fragment_path in fragment_paths {
+  for (index, fragment_path) in fragment_paths.into_iter().enumerate() {
     let fragment_path = current_dir.join(fragment_path);
-    let fragment = read_to_string(&fragment_path)?;
+    let fragment = read_to_string(&fragment_path)
+      .with_context(|| format!("failed to read WiX fragment {}", fragment_path.display()))?;
     let mut extensions = Vec::new();
     for cap in extension_regex.captures_iter(&fragment) {
       extensions.push(wix_toolset_path.join(format!("Wix{}.dll", &cap[1])));
     }
-    candle_inputs.push((fragment_path, extensions));
+    // fragments from different folders might share the same file name,
+    // so we prefix the object file with its index to prevent overwriting it
+    let output_file_name = format!(
+      "fragment{}_{}.wixobj",
+      index,
+      fragment_path
+        .file_stem()
+        .unwrap_or_default()
+        .to_string_lossy()
+    );
+    candle_inputs.push((fragment_path, output_file_name, extensions));
   }
 
   let mut fragment_extensions = Vec::new();
-  for (path, extensions) in candle_inputs {
+  for (path, output_file_name, extensions) in candle_inputs {
     fragment_extensions.extend(extensions.clone());
-    run_candle(settings, wix_toolset_path, &output_path, path, extensions)?;
+    run_candle(
+      settings,
+      wix_toolset_path,
+      &output_path,
+      path,
+      &output_file_name,
+      extensions,
+    )?;
   }
 
   let mut output_paths = Vec::new();
 
   for (language, language_config) in configured_languages.0 {
-    let language_metadata = language_map.get(&language).unwrap_or_else(|| {
-      panic!(
+    let language_metadata = language_map.get(&language).ok_or_else(|| {
+      let mut languages = language_map.keys().cloned().collect::<Vec<String>>();
+      languages.sort();
+      crate::Error::GenericError(format!(
         "Language {} not found. It must be one of {}",
         language,
-        language_map
-          .keys()
-          .cloned()
-          .collect::<Vec<String>>()
-          .join(", ")
-      )
-    });
+        languages.join(", ")
+      ))
+    })?;
 
     let locale_contents = match language_config.locale_path {
       Some(p) => read_to_string(p)?,
@@ -841,7 +961,7 @@ pub fn build_wix_app_installer(
       fileout.write_all(locale_contents.as_bytes())?;
     }
 
-    let arguments = vec![
+    let mut arguments = vec![
       format!(
         "-cultures:{}",
         if language == "en-US" {
@@ -854,6 +974,13 @@ pub fn build_wix_app_installer(
       locale_path.display().to_string(),
       "*.wixobj".into(),
     ];
+    if per_user_install {
+      // per-user installations write files to the user profile, which triggers
+      // ICE errors that only apply to roaming profiles
+      for ice in ["ICE38", "ICE64", "ICE91"] {
+        arguments.push(format!("-sice:{}", ice));
+      }
+    }
     let msi_output_path = output_path.join("output.msi");
     let msi_path = app_installer_output_path(settings, &language, updater)?;
     create_dir_all(msi_path.parent().unwrap())?;
@@ -868,7 +995,7 @@ pub fn build_wix_app_installer(
       &msi_output_path,
     )?;
     rename(&msi_output_path, &msi_path)?;
-    try_sign(&msi_path)?;
+    try_sign(&msi_path, settings)?;
     output_paths.push(msi_path);
   }
 
@@ -893,7 +1020,7 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
     std::fs::copy(binary_path, &dest)?;
 
     binaries.push(Binary {
-      guid: Uuid::new_v4().to_string(),
+      guid: generate_component_guid(settings, &dest_filename).to_string(),
       path: dest
         .into_os_string()
         .into_string()
@@ -907,7 +1034,7 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
   for bin in settings.binaries() {
     if !bin.main() {
       binaries.push(Binary {
-        guid: Uuid::new_v4().to_string(),
+        guid: generate_component_guid(settings, bin.name()).to_string(),
         path: settings
           .binary_path(bin)
           .into_os_string()
@@ -923,6 +1050,45 @@ fn generate_binaries_data(settings: &Settings) -> crate::Result<Vec<Binary>> {
   Ok(binaries)
 }
 
+fn generate_file_associations_data(settings: &Settings) -> crate::Result<Vec<FileAssociationData>> {
+  let regex = Regex::new(r"[^\w\d\.]")?;
+  let cwd = std::env::current_dir()?;
+  let mut associations = Vec::new();
+  for (index, association) in settings.file_associations().iter().enumerate() {
+    let name = association
+      .name
+      .clone()
+      .or_else(|| association.ext.first().cloned())
+      .unwrap_or_default();
+    let icon_path = association.icon.as_ref().map(|icon| {
+      cwd
+        .join(icon)
+        .into_os_string()
+        .into_string()
+        .expect("failed to read file association icon path")
+    });
+    associations.push(FileAssociationData {
+      prog_id: regex
+        .replace_all(&format!("{}.{}", settings.bundle_identifier(), name), "")
+        .to_string(),
+      description: association.description.clone().unwrap_or(name),
+      icon_id: icon_path
+        .as_ref()
+        .map(|_| format!("FileAssociationIcon{}", index)),
+      icon_path,
+      extensions: association
+        .ext
+        .iter()
+        .map(|ext| FileAssociationExtension {
+          ext: ext.clone(),
+          mime_type: association.mime_type.clone(),
+        })
+        .collect(),
+    });
+  }
+  Ok(associations)
+}
+
 #[derive(Serialize)]
 struct MergeModule {
   name: String,
@@ -980,14 +1146,15 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
 
     added_resources.push(resource_path.clone());
 
+    // split the resource path directories
+    let target_path = resource_relpath(&src);
+
+    let relative_path = target_path.to_string_lossy();
     let resource_entry = ResourceFile {
-      id: format!("I{}", Uuid::new_v4().as_simple()),
-      guid: Uuid::new_v4().to_string(),
+      id: format!("I{}", generate_guid(relative_path.as_bytes()).as_simple()),
+      guid: generate_component_guid(settings, &relative_path).to_string(),
       path: resource_path,
     };
-
-    // split the resource path directories
-    let target_path = resource_relpath(&src);
     let components_count = target_path.components().count();
     let directories = target_path
       .components()
@@ -1016,7 +1183,9 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
       .get_mut(&first_directory)
       .expect("Unable to handle resources");
 
-    let mut path = String::new();
+    // the full path is used to generate a stable and unique directory id
+    let mut path = first_directory.clone();
+    path.push(std::path::MAIN_SEPARATOR);
     // the first component is already parsed on `first_directory` so we skip(1)
     for directory in directories.into_iter().skip(1) {
       let directory_name = directory
@@ -1060,8 +1229,8 @@ fn generate_resource_data(settings: &Settings) -> crate::Result<ResourceMap> {
       .into_owned();
     if !added_resources.iter().any(|r| r.ends_with(&relative_path)) {
       dlls.push(ResourceFile {
-        id: format!("I{}", Uuid::new_v4().as_simple()),
-        guid: Uuid::new_v4().to_string(),
+        id: format!("I{}", generate_guid(relative_path.as_bytes()).as_simple()),
+        guid: generate_component_guid(settings, &relative_path).to_string(),
         path: resource_path,
       });
     }
@@ -0,0 +1,200 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::{
+  sign::{locate_windows_sdk_tool, try_sign},
+  util::copy_app_files,
+};
+use crate::bundle::{
+  common::CommandExt,
+  register_template_helpers,
+  settings::Settings,
+  template::{insert_template_vars, load_template},
+};
+use anyhow::{bail, Context};
+use handlebars::{to_json, Handlebars};
+use image::{imageops::FilterType, GenericImageView};
+use log::info;
+use std::{
+  collections::BTreeMap,
+  fs::{create_dir_all, remove_dir_all, write},
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+pub const MSIX_OUTPUT_FOLDER_NAME: &str = "msix";
+
+/// The logos referenced by the package manifest, with their dimensions.
+const LOGOS: &[(&str, u32)] = &[
+  ("StoreLogo.png", 50),
+  ("Square44x44Logo.png", 44),
+  ("Square150x150Logo.png", 150),
+];
+
+/// Runs all of the commands to build the MSIX package.
+/// Returns a vector of PathBuf that shows where the package was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target: {}",
+        target
+      )))
+    }
+  };
+
+  info!("Target: {}", arch);
+
+  let makeappx = locate_windows_sdk_tool("makeappx.exe")?.ok_or_else(|| {
+    anyhow::anyhow!("failed to find makeappx.exe, make sure the Windows SDK is installed")
+  })?;
+
+  let output_path = settings
+    .project_out_directory()
+    .join(MSIX_OUTPUT_FOLDER_NAME)
+    .join(arch);
+  if output_path.exists() {
+    remove_dir_all(&output_path)?;
+  }
+  let layout_path = output_path.join("layout");
+  create_dir_all(&layout_path)?;
+
+  // package layout
+  let main_binary_name = settings.main_binary_name().replace(".exe", "");
+  copy_app_files(settings, &layout_path)?;
+  generate_logos(settings, &layout_path.join("Assets"))?;
+
+  // package manifest
+  let msix = settings.windows().msix.clone().unwrap_or_default();
+  let bundle_id = settings.bundle_identifier();
+  let manufacturer = settings
+    .publisher()
+    .unwrap_or_else(|| bundle_id.split('.').nth(1).unwrap_or(bundle_id));
+
+  let mut data = BTreeMap::new();
+  data.insert("product_name", to_json(settings.product_name()));
+  data.insert("short_description", to_json(settings.short_description()));
+  data.insert(
+    "version",
+    to_json(package_version(settings.version_string())?),
+  );
+  data.insert("arch", to_json(arch));
+  data.insert("main_binary_name", to_json(&main_binary_name));
+  data.insert(
+    "identity_name",
+    to_json(msix.identity_name.as_deref().unwrap_or(bundle_id)),
+  );
+  data.insert(
+    "publisher",
+    to_json(
+      msix
+        .publisher
+        .clone()
+        .unwrap_or_else(|| format!("CN={}", manufacturer)),
+    ),
+  );
+  data.insert(
+    "publisher_display_name",
+    to_json(
+      msix
+        .publisher_display_name
+        .as_deref()
+        .unwrap_or(manufacturer),
+    ),
+  );
+  data.insert("capabilities", to_json(&msix.capabilities));
+
+  let mut handlebars = Handlebars::new();
+  register_template_helpers(&mut handlebars);
+  let template = load_template(
+    "AppxManifest.xml",
+    msix.template.as_deref(),
+    include_str!("./templates/AppxManifest.xml"),
+  )?;
+  handlebars
+    .register_template_string("AppxManifest.xml", &template)
+    .map_err(|e| e.to_string())
+    .expect("Failed to setup handlebar template");
+  insert_template_vars(settings, &mut data);
+  write(
+    layout_path.join("AppxManifest.xml"),
+    handlebars.render("AppxManifest.xml", &data)?,
+  )?;
+
+  let package_path = settings.project_out_directory().join(format!(
+    "bundle/{}/{}_{}_{}.msix",
+    MSIX_OUTPUT_FOLDER_NAME,
+    main_binary_name,
+    settings.version_string(),
+    arch,
+  ));
+  create_dir_all(package_path.parent().unwrap())?;
+
+  info!(action = "Running"; "makeappx to produce {}", package_path.display());
+
+  Command::new(makeappx)
+    .arg("pack")
+    .arg("/o")
+    .arg("/d")
+    .arg(&layout_path)
+    .arg("/p")
+    .arg(&package_path)
+    .output_ok()
+    .context("error running makeappx.exe")?;
+
+  try_sign(&package_path, settings)?;
+
+  Ok(vec![package_path])
+}
+
+/// Converts the app version to the `Major.Minor.Build.Revision` format required by MSIX.
+/// The revision is always 0, which the Microsoft Store reserves for its own use.
+fn package_version(version: &str) -> anyhow::Result<String> {
+  let version = semver::Version::parse(version).context("invalid app version")?;
+  if version.major > 65535 || version.minor > 65535 || version.patch > 65535 {
+    bail!("app version numbers cannot be greater than 65535 on MSIX packages");
+  }
+  if !(version.pre.is_empty() && version.build.is_empty()) {
+    bail!("app version cannot have build metadata or pre-release identifier on MSIX packages");
+  }
+  Ok(format!(
+    "{}.{}.{}.0",
+    version.major, version.minor, version.patch
+  ))
+}
+
+/// Generates the package logos from the largest PNG icon.
+fn generate_logos(settings: &Settings, assets_path: &Path) -> crate::Result<()> {
+  let mut largest_icon = None;
+  for icon_path in settings.icon_files() {
+    let icon_path = icon_path?;
+    if icon_path.extension() != Some(std::ffi::OsStr::new("png")) {
+      continue;
+    }
+    let icon = image::open(&icon_path)?;
+    let (width, height) = icon.dimensions();
+    if largest_icon
+      .as_ref()
+      .map_or(true, |(size, _)| width * height > *size)
+    {
+      largest_icon.replace((width * height, icon));
+    }
+  }
+
+  let (_, icon) = largest_icon.ok_or_else(|| {
+    anyhow::anyhow!("a PNG icon is required in `tauri.conf.json > tauri > bundle > icon` to generate the MSIX logos")
+  })?;
+
+  create_dir_all(assets_path)?;
+  for (name, size) in LOGOS {
+    icon
+      .resize_exact(*size, *size, FilterType::Lanczos3)
+      .save(assets_path.join(name))?;
+  }
+
+  Ok(())
+}
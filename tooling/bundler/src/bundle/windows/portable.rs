@@ -0,0 +1,89 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use super::util::{copy_app_files, zip_file_options};
+use crate::bundle::{common, settings::Settings};
+use log::info;
+use std::{
+  fs::{create_dir_all, remove_dir_all, File},
+  io::{Read, Write},
+  path::{Path, PathBuf},
+};
+
+pub const PORTABLE_OUTPUT_FOLDER_NAME: &str = "portable";
+/// The marker file written next to the executable.
+/// Apps can check for its existence to store their data relative to the executable instead of the user profile.
+pub const PORTABLE_MARKER_FILE_NAME: &str = ".portable";
+
+/// Packages the app in a zip archive that runs without installation.
+/// Returns a vector of PathBuf that shows where the archive was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    "aarch64" => "arm64",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target: {}",
+        target
+      )))
+    }
+  };
+
+  let output_path = settings
+    .project_out_directory()
+    .join(PORTABLE_OUTPUT_FOLDER_NAME)
+    .join(arch);
+  if output_path.exists() {
+    remove_dir_all(&output_path)?;
+  }
+  let app_path = output_path.join(settings.product_name());
+  create_dir_all(&app_path)?;
+
+  copy_app_files(settings, &app_path)?;
+  File::create(app_path.join(PORTABLE_MARKER_FILE_NAME))?;
+
+  let archive_path = settings.project_out_directory().join(format!(
+    "bundle/{}/{}_{}_{}_portable.zip",
+    PORTABLE_OUTPUT_FOLDER_NAME,
+    settings.main_binary_name().replace(".exe", ""),
+    settings.version_string(),
+    arch,
+  ));
+
+  info!(action = "Bundling"; "{}", archive_path.display());
+
+  create_zip(&app_path, &archive_path)?;
+
+  Ok(vec![archive_path])
+}
+
+/// Creates a zip archive of the given directory, including the directory itself.
+fn create_zip(src_dir: &Path, dst_file: &Path) -> crate::Result<()> {
+  let writer = common::create_file(dst_file)?;
+  let mut zip = zip::ZipWriter::new(writer);
+  let options = zip_file_options().compression_method(zip::CompressionMethod::Deflated);
+
+  let base_dir = src_dir.parent().expect("No data in parent");
+  // sorted to make the archive reproducible
+  for entry in walkdir::WalkDir::new(src_dir).sort_by(|a, b| a.file_name().cmp(b.file_name())) {
+    let entry = entry?;
+    let name = entry
+      .path()
+      .strip_prefix(base_dir)?
+      .to_string_lossy()
+      .replace('\\', "/");
+    if entry.file_type().is_dir() {
+      zip.add_directory(name, options)?;
+    } else {
+      zip.start_file(name, options)?;
+      let mut buffer = Vec::new();
+      File::open(entry.path())?.read_to_end(&mut buffer)?;
+      zip.write_all(&buffer)?;
+    }
+  }
+
+  zip.finish()?;
+  Ok(())
+}
@@ -3,7 +3,7 @@
 // SPDX-License-Identifier: Apache-2.0
 // SPDX-License-Identifier: MIT
 
-use crate::bundle::common::CommandExt;
+use crate::{bundle::common::CommandExt, Settings};
 use bitness::{self, Bitness};
 use log::{debug, info};
 use std::{
@@ -15,16 +15,32 @@ use winreg::{
   RegKey,
 };
 
+/// The certificate used to sign a file.
+pub enum SignCertificate {
+  /// A certificate from the Windows certificate store, identified by its SHA1 hash.
+  Thumbprint(String),
+  /// A PFX certificate file and its password.
+  File {
+    path: PathBuf,
+    password: Option<String>,
+  },
+}
+
 pub struct SignParams {
   pub product_name: String,
   pub digest_algorithm: String,
-  pub certificate_thumbprint: String,
+  pub certificate: SignCertificate,
   pub timestamp_url: Option<String>,
   pub tsp: bool,
 }
 
 // sign code forked from https://github.com/forbjok/rust-codesign
 fn locate_signtool() -> crate::Result<PathBuf> {
+  locate_windows_sdk_tool("signtool.exe")?.ok_or(crate::Error::SignToolNotFound)
+}
+
+/// Finds a tool shipped with the Windows SDK, e.g. `signtool.exe` or `makeappx.exe`.
+pub fn locate_windows_sdk_tool(tool: &str) -> crate::Result<Option<PathBuf>> {
   const INSTALLED_ROOTS_REGKEY_PATH: &str = r"SOFTWARE\Microsoft\Windows Kits\Installed Roots";
   const KITS_ROOT_REGVALUE_NAME: &str = r"KitsRoot10";
 
@@ -68,43 +84,62 @@ fn locate_signtool() -> crate::Result<PathBuf> {
   For Windows SDK 10 versions earlier than v10.0.15063.468, signtool will be located there. */
   kit_bin_paths.push(kits_root_10_bin_path);
 
-  // Choose which version of SignTool to use based on OS bitness
+  // Choose which version of the tool to use based on OS bitness
   let arch_dir = match bitness::os_bitness().expect("failed to get os bitness") {
     Bitness::X86_32 => "x86",
     Bitness::X86_64 => "x64",
     _ => return Err(crate::Error::UnsupportedBitness),
   };
 
-  /* Iterate through all bin paths, checking for existence of a SignTool executable. */
+  /* Iterate through all bin paths, checking for existence of the tool executable. */
   for kit_bin_path in &kit_bin_paths {
-    /* Construct SignTool path. */
-    let signtool_path = kit_bin_path.join(arch_dir).join("signtool.exe");
+    /* Construct the tool path. */
+    let tool_path = kit_bin_path.join(arch_dir).join(tool);
 
-    /* Check if SignTool exists at this location. */
-    if signtool_path.exists() {
-      // SignTool found. Return it.
-      return Ok(signtool_path);
+    /* Check if the tool exists at this location. */
+    if tool_path.exists() {
+      // Tool found. Return it.
+      return Ok(Some(tool_path));
     }
   }
 
-  Err(crate::Error::SignToolNotFound)
+  Ok(None)
 }
 
 pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
   // Convert path to string reference, as we need to pass it as a command-line parameter to signtool
   let path_str = path.as_ref().to_str().unwrap();
 
-  info!(action = "Signing"; "{} with identity \"{}\"", path_str, params.certificate_thumbprint);
+  match &params.certificate {
+    SignCertificate::Thumbprint(thumbprint) => {
+      info!(action = "Signing"; "{} with identity \"{}\"", path_str, thumbprint)
+    }
+    SignCertificate::File { path, .. } => {
+      info!(action = "Signing"; "{} with certificate \"{}\"", path_str, path.display())
+    }
+  }
 
   // Construct SignTool command
   let signtool = locate_signtool()?;
 
   debug!("Running signtool {:?}", signtool);
 
+  let mut secrets = Vec::new();
   let mut cmd = Command::new(signtool);
   cmd.arg("sign");
   cmd.args(&["/fd", &params.digest_algorithm]);
-  cmd.args(&["/sha1", &params.certificate_thumbprint]);
+  match &params.certificate {
+    SignCertificate::Thumbprint(thumbprint) => {
+      cmd.args(&["/sha1", thumbprint]);
+    }
+    SignCertificate::File { path, password } => {
+      cmd.arg("/f").arg(path);
+      if let Some(password) = password {
+        cmd.args(&["/p", password]);
+        secrets.push(password.as_str());
+      }
+    }
+  }
   cmd.args(&["/d", &params.product_name]);
 
   if let Some(ref timestamp_url) = params.timestamp_url {
@@ -119,10 +154,66 @@ pub fn sign<P: AsRef<Path>>(path: P, params: &SignParams) -> crate::Result<()> {
   cmd.arg(path_str);
 
   // Execute SignTool command
-  let output = cmd.output_ok()?;
+  let output = cmd.output_ok_redacted(&secrets)?;
 
   let stdout = String::from_utf8_lossy(output.stdout.as_slice()).into_owned();
   info!("{:?}", stdout);
 
   Ok(())
 }
+
+/// Signs the given file with the certificate configured in the Windows settings, if any.
+pub fn try_sign(file_path: &Path, settings: &Settings) -> crate::Result<()> {
+  let certificate = if let Some(certificate_thumbprint) = &settings.windows().certificate_thumbprint
+  {
+    Some(SignCertificate::Thumbprint(
+      certificate_thumbprint.to_string(),
+    ))
+  } else {
+    settings
+      .windows()
+      .certificate_path
+      .as_ref()
+      .map(|path| SignCertificate::File {
+        path: path.clone(),
+        password: settings.windows().certificate_password.clone(),
+      })
+  };
+  if let Some(certificate) = certificate {
+    info!(action = "Signing"; "{}", file_path.display());
+    sign(
+      file_path,
+      &SignParams {
+        product_name: settings.product_name().into(),
+        digest_algorithm: settings
+          .windows()
+          .digest_algorithm
+          .as_ref()
+          .map(|algorithm| algorithm.to_string())
+          .unwrap_or_else(|| "sha256".to_string()),
+        certificate,
+        timestamp_url: settings
+          .windows()
+          .timestamp_url
+          .as_ref()
+          .map(|url| url.to_string()),
+        tsp: settings.windows().tsp,
+      },
+    )?;
+  }
+  Ok(())
+}
+
+/// Signs the app binaries and the sidecars in place.
+///
+/// This runs once before the Windows package types are bundled concurrently,
+/// so the bundlers copy the signed executables instead of each signing them again.
+pub fn sign_binaries(settings: &Settings) -> crate::Result<()> {
+  for bin in settings.binaries() {
+    try_sign(&settings.binary_path(bin), settings)?;
+  }
+  for src in settings.external_binaries() {
+    try_sign(&src?, settings)?;
+  }
+  Ok(())
+}
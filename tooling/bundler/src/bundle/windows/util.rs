@@ -0,0 +1,134 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::bundle::{common, settings::Settings};
+use anyhow::Context;
+use log::{info, warn};
+use sha2::Digest;
+use std::{path::Path, time::Duration};
+use tauri_utils::config::WebviewInstallMode;
+
+pub const WEBVIEW2_BOOTSTRAPPER_URL: &str = "https://go.microsoft.com/fwlink/p/?LinkId=2124703";
+
+/// Number of attempts made by [`download`] when `TAURI_BUNDLER_DOWNLOAD_RETRIES` is not set.
+const DEFAULT_DOWNLOAD_RETRIES: u32 = 3;
+
+/// Downloads the file at the given URL.
+///
+/// The `HTTPS_PROXY`, `HTTP_PROXY` and `NO_PROXY` environment variables are honored,
+/// and failed requests are retried with an exponential backoff up to `TAURI_BUNDLER_DOWNLOAD_RETRIES` times.
+pub fn download(url: &str) -> crate::Result<Vec<u8>> {
+  let retries = std::env::var("TAURI_BUNDLER_DOWNLOAD_RETRIES")
+    .ok()
+    .and_then(|retries| retries.parse().ok())
+    .unwrap_or(DEFAULT_DOWNLOAD_RETRIES);
+
+  let mut attempt = 0;
+  loop {
+    info!(action = "Downloading"; "{}", url);
+    let result = attohttpc::get(url)
+      .proxy_settings(attohttpc::ProxySettings::from_env())
+      .send()
+      .and_then(|response| response.error_for_status())
+      .and_then(|response| response.bytes());
+    match result {
+      Ok(data) => return Ok(data),
+      // client errors won't go away by retrying
+      Err(e) if attempt < retries && !is_client_error(&e) => {
+        attempt += 1;
+        let delay = Duration::from_secs(1 << attempt.min(6));
+        warn!(
+          "failed to download {}: {}, retrying in {}s ({}/{})",
+          url,
+          e,
+          delay.as_secs(),
+          attempt,
+          retries
+        );
+        std::thread::sleep(delay);
+      }
+      Err(e) => return Err(e.into()),
+    }
+  }
+}
+
+fn is_client_error(error: &attohttpc::Error) -> bool {
+  matches!(error.kind(), attohttpc::ErrorKind::StatusCode(status) if status.is_client_error())
+}
+
+/// Function used to download Wix. Checks SHA256 to verify the download.
+///
+/// If `TAURI_BUNDLER_MIRROR` is set, the file is downloaded from that base URL instead,
+/// with the same file name as the original URL. The hash is verified either way.
+pub fn download_and_verify(url: &str, hash: &str) -> crate::Result<Vec<u8>> {
+  let url = mirror_url(url);
+  let data = download(&url)?;
+  info!("validating hash");
+
+  let mut hasher = sha2::Sha256::new();
+  hasher.update(&data);
+
+  let url_hash = hasher.finalize().to_vec();
+  let expected_hash = hex::decode(hash)?;
+
+  if expected_hash == url_hash {
+    Ok(data)
+  } else {
+    Err(crate::Error::HashError)
+  }
+}
+
+/// Replaces the origin of the URL with the `TAURI_BUNDLER_MIRROR` base URL, if set.
+fn mirror_url(url: &str) -> String {
+  match std::env::var("TAURI_BUNDLER_MIRROR") {
+    Ok(mirror) if !mirror.is_empty() => {
+      let file_name = url.rsplit('/').next().unwrap_or(url);
+      format!("{}/{}", mirror.trim_end_matches('/'), file_name)
+    }
+    _ => url.to_string(),
+  }
+}
+
+/// Copies the app executables, resources and Webview2 fixed runtime to a directory,
+/// laid out as they are installed.
+pub fn copy_app_files(settings: &Settings, dest: &Path) -> crate::Result<()> {
+  for bin in settings.binaries() {
+    let path = settings.binary_path(bin);
+    let file_name = if bin.main() {
+      format!("{}.exe", settings.main_binary_name().replace(".exe", "")).into()
+    } else {
+      path.file_name().unwrap().to_os_string()
+    };
+    std::fs::copy(&path, dest.join(file_name))
+      .with_context(|| format!("failed to copy {}", path.display()))?;
+  }
+  settings.copy_binaries(dest)?;
+  settings.copy_resources(dest)?;
+
+  // the Webview2 installer can't run from a portable or packaged app, so only a fixed runtime can be shipped with it
+  let fixed_runtime_path = match &settings.windows().webview_install_mode {
+    WebviewInstallMode::FixedRuntime { path } => Some(path.clone()),
+    _ => settings.windows().webview_fixed_runtime_path.clone(),
+  };
+  if let Some(path) = fixed_runtime_path {
+    let dir_name = path
+      .file_name()
+      .ok_or_else(|| anyhow::anyhow!("invalid Webview2 fixed runtime path {}", path.display()))?;
+    common::copy_dir(&path, &dest.join(dir_name))?;
+  }
+
+  Ok(())
+}
+
+/// Default zip entry options, using the `SOURCE_DATE_EPOCH` as modification time if set.
+pub fn zip_file_options() -> zip::write::FileOptions {
+  let options = zip::write::FileOptions::default();
+  match common::source_date_epoch()
+    .and_then(|epoch| time::OffsetDateTime::from_unix_timestamp(epoch as i64).ok())
+    .and_then(|date| zip::DateTime::try_from(date).ok())
+  {
+    Some(date) => options.last_modified_time(date),
+    None => options,
+  }
+}
@@ -0,0 +1,51 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// The GUIDs of the Windows Installer (.msi), shared by the WiX and wixl backends
+// so installers built by either one upgrade each other.
+
+use super::Settings;
+use anyhow::Context;
+use uuid::Uuid;
+
+// A v4 UUID that was generated specifically for tauri-bundler, to be used as a
+// namespace for generating v5 UUIDs from bundle identifier strings.
+const UUID_NAMESPACE: [u8; 16] = [
+  0xfd, 0x85, 0x95, 0xa8, 0x17, 0xa3, 0x47, 0x4e, 0xa6, 0x16, 0x76, 0x14, 0x8d, 0xfa, 0x0c, 0x7b,
+];
+
+/// Returns the configured upgrade code, or the one derived from the main binary name.
+pub fn upgrade_code(settings: &Settings) -> crate::Result<Uuid> {
+  match settings
+    .windows()
+    .wix
+    .as_ref()
+    .and_then(|wix| wix.upgrade_code.as_ref())
+  {
+    Some(upgrade_code) => Ok(
+      Uuid::parse_str(upgrade_code)
+        .with_context(|| format!("invalid WiX upgrade code `{}`", upgrade_code))?,
+    ),
+    None => Ok(Uuid::new_v5(
+      &Uuid::NAMESPACE_DNS,
+      format!("{}.app.x64", settings.main_binary_name()).as_bytes(),
+    )),
+  }
+}
+
+/// Generates the UUID for the Wix template.
+pub fn generate_package_guid(settings: &Settings) -> Uuid {
+  generate_guid(settings.bundle_identifier().as_bytes())
+}
+
+/// Generates a stable component GUID for an installed file, so upgrades replace the same components.
+pub fn generate_component_guid(settings: &Settings, path: &str) -> Uuid {
+  generate_guid(format!("{}:{}", settings.bundle_identifier(), path).as_bytes())
+}
+
+/// Generates a GUID.
+pub fn generate_guid(key: &[u8]) -> Uuid {
+  let namespace = Uuid::from_bytes(UUID_NAMESPACE);
+  Uuid::new_v5(&namespace, key)
+}
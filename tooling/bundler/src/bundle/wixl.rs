@@ -0,0 +1,383 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+// Builds the Windows Installer (.msi) outside of Windows with `wixl` from msitools
+// (https://wiki.gnome.org/msitools), which understands a subset of the WiX v3 source format.
+// The installer is rendered from the `main.wixl.wxs` template: it copies the binaries and resources
+// to the Program Files folder and adds the Start Menu and desktop shortcuts, without any installer UI.
+
+use super::{
+  common::CommandExt,
+  register_template_helpers,
+  template::{insert_template_vars, load_template},
+  wix_guid::{generate_component_guid, generate_guid, generate_package_guid, upgrade_code},
+};
+use crate::{bundle::settings::WindowsInstallScope, Settings};
+use anyhow::Context;
+use handlebars::{to_json, Handlebars};
+use log::{info, warn};
+use tauri_utils::{config::WebviewInstallMode, resources::resource_relpath};
+
+use std::{
+  collections::BTreeMap,
+  fmt::Write,
+  fs,
+  path::{Path, PathBuf},
+  process::Command,
+};
+
+/// A directory of the installation, with the files installed in it.
+#[derive(Default)]
+struct Directory {
+  files: Vec<(String, PathBuf)>,
+  directories: BTreeMap<String, Directory>,
+}
+
+impl Directory {
+  fn insert(&mut self, target: &Path, source: PathBuf) {
+    let mut directory = self;
+    if let Some(parent) = target.parent() {
+      for component in parent.components() {
+        directory = directory
+          .directories
+          .entry(component.as_os_str().to_string_lossy().into_owned())
+          .or_default();
+      }
+    }
+    directory.files.push((
+      target
+        .file_name()
+        .expect("failed to get file name")
+        .to_string_lossy()
+        .into_owned(),
+      source,
+    ));
+  }
+}
+
+/// Bundles the project.
+/// Returns a vector of PathBuf that shows where the MSI was created.
+pub fn bundle_project(settings: &Settings) -> crate::Result<Vec<PathBuf>> {
+  // the binary arch alone can't tell a Linux executable apart from a Windows one
+  if !settings.target().contains("windows") {
+    return Err(crate::Error::GenericError(format!(
+      "MSI installers can only be built for Windows targets, but the target is {}; pass `--target x86_64-pc-windows-gnu` or similar",
+      settings.target()
+    )));
+  }
+
+  let arch = match settings.binary_arch() {
+    "x86_64" => "x64",
+    "x86" => "x86",
+    target => {
+      return Err(crate::Error::ArchError(format!(
+        "unsupported target for wixl: {}",
+        target
+      )))
+    }
+  };
+
+  if settings.windows().icon_path.as_os_str().is_empty() {
+    return Err(crate::Error::GenericError(
+      "the bundle config must have a `.ico` icon to build the MSI installer".into(),
+    ));
+  }
+
+  check_unsupported_config(settings)?;
+
+  if settings.windows().webview_install_mode != WebviewInstallMode::Skip {
+    warn!(
+      "wixl can't install the Webview2 runtime, make sure it is available on the target machines"
+    );
+  }
+
+  let output_path = settings.project_out_directory().join("wixl").join(arch);
+  if output_path.exists() {
+    fs::remove_dir_all(&output_path)?;
+  }
+  fs::create_dir_all(&output_path)?;
+
+  let main_binary_name = settings.main_binary_name().replace(".exe", "");
+  let msi_path = settings.project_out_directory().join(format!(
+    "bundle/msi/{}_{}_{}_en-US.msi",
+    main_binary_name,
+    settings.version_string(),
+    arch
+  ));
+  fs::create_dir_all(msi_path.parent().unwrap())?;
+
+  info!(action = "Bundling"; "{} ({})", msi_path.display(), arch);
+
+  let cwd = std::env::current_dir()?;
+  let mut install_dir = Directory::default();
+  for bin in settings.binaries() {
+    install_dir.insert(Path::new(bin.name()), settings.binary_path(bin));
+  }
+  for src in settings.external_binaries() {
+    let src = src?;
+    let name = src
+      .file_name()
+      .expect("failed to extract external binary filename")
+      .to_string_lossy()
+      .replace(&format!("-{}", settings.target()), "");
+    install_dir.insert(Path::new(&name), cwd.join(&src));
+  }
+  for src in settings.resource_files() {
+    let src = src?;
+    install_dir.insert(&resource_relpath(&src), cwd.join(&src));
+  }
+
+  let wxs_path = output_path.join("main.wxs");
+  generate_wxs_file(settings, arch, &install_dir, &wxs_path)
+    .with_context(|| "Failed to create the WiX source file")?;
+
+  info!(action = "Running"; "wixl to produce {}", msi_path.display());
+
+  Command::new("wixl")
+    .arg("--arch")
+    .arg(arch)
+    .arg("--output")
+    .arg(&msi_path)
+    .arg(&wxs_path)
+    .output_ok()
+    .context("error running wixl, make sure msitools is installed")?;
+
+  Ok(vec![msi_path])
+}
+
+/// Fails on the configuration that only the WiX backend implements,
+/// instead of silently building an installer without it.
+fn check_unsupported_config(settings: &Settings) -> crate::Result<()> {
+  let mut unsupported = Vec::new();
+  if settings.windows().install_scope == WindowsInstallScope::User {
+    unsupported.push("`tauri > bundle > windows > installScope` set to `user`");
+  }
+  if !settings.file_associations().is_empty() {
+    unsupported.push("`tauri > bundle > fileAssociations`");
+  }
+  if !settings.deep_link_schemes().is_empty() {
+    unsupported.push("`tauri > bundle > deepLink`");
+  }
+  if settings.service().is_some() {
+    unsupported.push("`tauri > bundle > service`");
+  }
+  if let Some(wix) = &settings.windows().wix {
+    if wix.template.is_some() {
+      unsupported.push("`tauri > bundle > windows > wix > template`");
+    }
+    if !(wix.fragment_paths.is_empty()
+      && wix.component_group_refs.is_empty()
+      && wix.component_refs.is_empty()
+      && wix.feature_group_refs.is_empty()
+      && wix.feature_refs.is_empty()
+      && wix.merge_refs.is_empty())
+    {
+      unsupported.push("`tauri > bundle > windows > wix` fragments and references");
+    }
+    if wix.install_dir.is_some() {
+      unsupported.push("`tauri > bundle > windows > wix > installDir`");
+    }
+  }
+
+  if unsupported.is_empty() {
+    Ok(())
+  } else {
+    Err(crate::Error::GenericError(format!(
+      "the wixl MSI backend does not support {}, use the WiX backend on Windows instead",
+      unsupported.join(", ")
+    )))
+  }
+}
+
+/// Generates the WiX source file describing the installer.
+fn generate_wxs_file(
+  settings: &Settings,
+  arch: &str,
+  install_dir: &Directory,
+  wxs_path: &Path,
+) -> crate::Result<()> {
+  let mut data = BTreeMap::new();
+
+  let bundle_id = settings.bundle_identifier();
+  data.insert("product_name", to_json(settings.product_name()));
+  data.insert(
+    "manufacturer",
+    to_json(
+      settings
+        .publisher()
+        .unwrap_or_else(|| bundle_id.split('.').nth(1).unwrap_or(bundle_id)),
+    ),
+  );
+  data.insert("version", to_json(settings.version_string()));
+  data.insert("short_description", to_json(settings.short_description()));
+  data.insert("upgrade_code", to_json(upgrade_code(settings)?.to_string()));
+  data.insert("icon_path", to_json(&settings.windows().icon_path));
+  data.insert("main_binary_name", to_json(settings.main_binary_name()));
+  data.insert(
+    "program_files_folder",
+    to_json(if arch == "x64" {
+      "ProgramFiles64Folder"
+    } else {
+      "ProgramFilesFolder"
+    }),
+  );
+
+  let wix = settings.windows().wix.as_ref();
+  data.insert(
+    "shortcut_name",
+    to_json(
+      wix
+        .and_then(|wix| wix.shortcut_name.as_deref())
+        .unwrap_or_else(|| settings.product_name()),
+    ),
+  );
+  data.insert(
+    "start_menu_folder",
+    to_json(
+      wix
+        .and_then(|wix| wix.start_menu_folder.as_deref())
+        .unwrap_or_else(|| settings.product_name()),
+    ),
+  );
+  data.insert(
+    "skip_desktop_shortcut",
+    to_json(wix.map(|wix| wix.skip_desktop_shortcut).unwrap_or_default()),
+  );
+  data.insert(
+    "shortcut_guid",
+    to_json(generate_package_guid(settings).to_string()),
+  );
+  data.insert(
+    "desktop_shortcut_guid",
+    to_json(generate_component_guid(settings, "ApplicationDesktopShortcut").to_string()),
+  );
+
+  let mut install_dir_contents = String::new();
+  let mut component_ids = Vec::new();
+  write_directory_contents(
+    &mut install_dir_contents,
+    settings,
+    install_dir,
+    Path::new(""),
+    &mut component_ids,
+  );
+  data.insert("install_dir_contents", to_json(install_dir_contents));
+  data.insert("component_ids", to_json(component_ids));
+
+  let mut handlebars = Handlebars::new();
+  register_template_helpers(&mut handlebars);
+  handlebars
+    .register_template_string(
+      "main.wixl.wxs",
+      load_template(
+        "main.wixl.wxs",
+        None,
+        include_str!("windows/templates/main.wixl.wxs"),
+      )?,
+    )
+    .map_err(|e| e.to_string())
+    .expect("Failed to setup handlebar template");
+  insert_template_vars(settings, &mut data);
+  fs::write(wxs_path, handlebars.render("main.wixl.wxs", &data)?)?;
+  Ok(())
+}
+
+/// Writes a component for each file of the directory, then its subdirectories.
+fn write_directory_contents(
+  contents: &mut String,
+  settings: &Settings,
+  directory: &Directory,
+  path: &Path,
+  component_ids: &mut Vec<String>,
+) {
+  for (name, source) in &directory.files {
+    let relative_path = path.join(name).to_string_lossy().into_owned();
+    let id = format!("I{}", generate_guid(relative_path.as_bytes()).as_simple());
+    write!(
+      contents,
+      r#"<Component Id="{id}" Guid="{guid}"><File Id="PathFile_{id}" Name="{name}" Source="{source}" KeyPath="yes"/></Component>"#,
+      id = id,
+      guid = generate_component_guid(settings, &relative_path),
+      name = escape(name),
+      source = escape(&source.to_string_lossy()),
+    )
+    .unwrap();
+    component_ids.push(id);
+  }
+  for (name, subdirectory) in &directory.directories {
+    let path = path.join(name);
+    write!(
+      contents,
+      r#"<Directory Id="I{}" Name="{}">"#,
+      generate_guid(path.to_string_lossy().as_bytes()).as_simple(),
+      escape(name)
+    )
+    .unwrap();
+    write_directory_contents(contents, settings, subdirectory, &path, component_ids);
+    contents.push_str("</Directory>");
+  }
+}
+
+fn escape(text: &str) -> String {
+  text
+    .replace('&', "&amp;")
+    .replace('<', "&lt;")
+    .replace('>', "&gt;")
+    .replace('"', "&quot;")
+}
+
+#[cfg(test)]
+mod tests {
+  use super::{bundle_project, check_unsupported_config, generate_wxs_file, Directory};
+  use crate::bundle::settings::{test_settings, BundleSettings};
+  use std::{fs, path::Path};
+
+  #[test]
+  fn generates_wxs_file() {
+    let tmp = tempfile::tempdir().expect("unable to create temp dir");
+    let settings = test_settings("x86_64-pc-windows-msvc", Default::default());
+    let mut install_dir = Directory::default();
+    install_dir.insert(Path::new("tauri-app.exe"), "target/tauri-app.exe".into());
+    install_dir.insert(
+      Path::new("assets/logo & icon.png"),
+      "assets/logo & icon.png".into(),
+    );
+
+    let wxs_path = tmp.path().join("main.wxs");
+    generate_wxs_file(&settings, "x64", &install_dir, &wxs_path).unwrap();
+    let wxs = fs::read_to_string(wxs_path).unwrap();
+
+    assert!(wxs.contains(r#"Name="Tauri App""#));
+    assert!(wxs.contains(r#"Version="1.2.3""#));
+    assert!(wxs.contains(r#"<Directory Id="ProgramFiles64Folder">"#));
+    assert!(wxs.contains(r#"Key="Software\tauri\Tauri App""#));
+    assert!(wxs.contains(r#"Name="tauri-app.exe" Source="target/tauri-app.exe""#));
+    assert!(wxs.contains(r#"Name="logo &amp; icon.png" Source="assets/logo &amp; icon.png""#));
+    assert!(wxs.contains(r#"Name="assets">"#));
+    // both files and the desktop shortcut are part of the feature
+    assert_eq!(wxs.matches("<ComponentRef Id=\"I").count(), 2);
+    assert!(wxs.contains(r#"<ComponentRef Id="ApplicationDesktopShortcut"/>"#));
+  }
+
+  #[test]
+  fn rejects_non_windows_targets() {
+    let settings = test_settings("x86_64-unknown-linux-gnu", Default::default());
+    assert!(bundle_project(&settings).is_err());
+  }
+
+  #[test]
+  fn rejects_unsupported_config() {
+    let settings = test_settings("x86_64-pc-windows-msvc", Default::default());
+    assert!(check_unsupported_config(&settings).is_ok());
+
+    let settings = test_settings(
+      "x86_64-pc-windows-msvc",
+      BundleSettings {
+        deep_link_schemes: vec!["tauri".into()],
+        ..Default::default()
+      },
+    );
+    assert!(check_unsupported_config(&settings).is_err());
+  }
+}
@@ -49,7 +49,7 @@ pub enum Error {
   #[error("`{0}`")]
   JsonError(#[from] serde_json::error::Error),
   /// Regex error.
-  #[cfg(any(target_os = "macos", windows))]
+  #[cfg(windows)]
   #[error("`{0}`")]
   RegexError(#[from] regex::Error),
   /// Failed to perform HTTP request.
@@ -82,6 +82,27 @@ pub enum Error {
   /// Generic error.
   #[error("`{0}`")]
   GenericError(String),
+  /// A command exited with a failure status.
+  #[error("failed to run {program} ({status})")]
+  CommandFailed {
+    /// The program that was run.
+    program: String,
+    /// The exit status of the program.
+    status: std::process::ExitStatus,
+    /// The captured standard output.
+    stdout: String,
+    /// The captured standard error.
+    stderr: String,
+  },
+  /// A WiX tool reported errors.
+  #[cfg(target_os = "windows")]
+  #[error("{tool} failed:\n{}", .diagnostics.join("\n"))]
+  WixError {
+    /// The WiX tool, e.g. `candle.exe`.
+    tool: &'static str,
+    /// The error messages printed by the tool, e.g. `main.wxs(12) : error CNDL0104 : Not a valid source file`.
+    diagnostics: Vec<String>,
+  },
   /// No bundled project found for the updater.
   #[error("Unable to find a bundled project for the updater")]
   UnableToFindProject,
@@ -9,18 +9,50 @@ use crate::{
     config::{get as get_config, AppUrl, HookCommand, WindowUrl, MERGE_CONFIG_EXTENSION_NAME},
     updater_signature::{read_key_from_file, secret_key as updater_secret_key, sign_file},
   },
-  interface::{AppInterface, AppSettings, Interface},
+  interface::{rust::is_debug_profile, AppInterface, AppSettings, Interface},
   CommandExt, Result,
 };
 use anyhow::{bail, Context};
-use clap::{ArgAction, Parser};
+use clap::{ArgAction, Parser, ValueEnum};
 use log::{debug, error, info, warn};
+use serde::Serialize;
+use sha2::{Digest, Sha256};
 use std::{
+  collections::HashMap,
   env::{set_current_dir, var_os},
+  fs::File,
+  io::BufReader,
   path::{Path, PathBuf},
   process::Command,
 };
-use tauri_bundler::bundle::{bundle_project, Bundle, PackageType};
+use tauri_bundler::bundle::{bundle_project, write_checksums, Bundle, PackageType};
+
+/// The output format of the build command.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+  /// Human readable logs.
+  Text,
+  /// Progress events as JSON lines on stderr and a JSON document listing the artifacts on stdout.
+  Json,
+}
+
+/// The tool used to build the Windows Installer (.msi) bundle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum MsiBackend {
+  /// The WiX Toolset, only available on Windows.
+  Wix,
+  /// `wixl` from msitools, available on Linux and macOS.
+  Wixl,
+}
+
+impl From<MsiBackend> for tauri_bundler::MsiBackend {
+  fn from(backend: MsiBackend) -> Self {
+    match backend {
+      MsiBackend::Wix => Self::Wix,
+      MsiBackend::Wixl => Self::Wixl,
+    }
+  }
+}
 
 #[derive(Debug, Clone, Parser)]
 #[clap(about = "Tauri build")]
@@ -41,48 +73,120 @@ pub struct Options {
   /// Space or comma separated list of features to activate
   #[clap(short, long, action = ArgAction::Append, num_args(0..))]
   pub features: Option<Vec<String>>,
+  /// Do not activate the `default` feature
+  #[clap(long)]
+  pub no_default_features: bool,
+  /// Cargo profile to build with, instead of `release` (or `dev` with `--debug`)
+  ///
+  /// The app is bundled in debug mode if the profile is `dev`, `test` or a custom profile that inherits from them.
+  #[clap(long, conflicts_with = "debug")]
+  pub profile: Option<String>,
   /// Space or comma separated list of bundles to package.
   ///
-  /// Each bundle must be one of `deb`, `appimage`, `msi`, `app` or `dmg` on MacOS and `updater` on all platforms.
+  /// Each bundle must be one of `deb`, `rpm`, `flatpak`, `snap`, `appimage`, `msi` (on Windows, or with `--msi-backend wixl`), `nsis`, `msix`, `portable`, `app`, `dmg` or `pkg` on MacOS and `updater` on all platforms.
   /// If `none` is specified, the bundler will be skipped.
   ///
   /// Note that the `updater` bundle is not automatically added so you must specify it if the updater is enabled.
   #[clap(short, long, action = ArgAction::Append, num_args(0..))]
   pub bundles: Option<Vec<String>>,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// JSON string or path to JSON file to merge with tauri.conf.json.
+  ///
+  /// Can be specified multiple times, the values are merged in order.
+  #[clap(short, long, action = ArgAction::Append)]
+  pub config: Vec<String>,
+  /// Output format.
+  ///
+  /// With `json`, the progress is streamed as JSON lines on stderr
+  /// and a JSON document listing every produced artifact is printed to stdout.
+  #[clap(long, value_enum, default_value_t = OutputFormat::Text)]
+  pub format: OutputFormat,
+  /// Tool used to build the `msi` bundle.
+  ///
+  /// `wixl` (from msitools) builds the installer on Linux and macOS,
+  /// but it does not support the installer UI, the WiX template and fragments or the WebView2 installation.
+  /// Its own `main.wixl.wxs` template can be replaced by a file in the `templates` folder.
+  #[clap(long, value_enum, default_value_t = MsiBackend::Wix)]
+  pub msi_backend: MsiBackend,
+  /// Base URL the updater bundles are uploaded to.
+  ///
+  /// When set, a `latest.json` manifest listing the signed updater bundles under this URL
+  /// is written to the bundle directory, ready to be served as a static updater endpoint.
+  #[clap(long)]
+  pub updater_base_url: Option<String>,
+  /// Release notes written to the `latest.json` updater manifest.
+  #[clap(long, requires = "updater_base_url")]
+  pub updater_notes: Option<String>,
+  /// Updater bundle of the previous release to generate a delta update against.
+  ///
+  /// A signed zstd compressed bsdiff patch from that bundle to the new updater bundle is written next to it
+  /// as `<bundle>.patch`, and listed in the `latest.json` manifest with the SHA256 hash of the bundle it applies to.
+  ///
+  /// The Tauri updater does not apply patches: it always downloads the full bundle. The patch metadata is meant
+  /// for custom update servers and clients that apply the patch themselves.
+  #[clap(long)]
+  pub delta_from: Option<PathBuf>,
   /// Command line arguments passed to the runner
   pub args: Vec<String>,
 }
 
+/// An artifact produced by the bundler, as printed by `--format json`.
+#[derive(Debug, Serialize)]
+struct Artifact {
+  path: PathBuf,
+  format: &'static str,
+  arch: String,
+  /// The SHA256 hash of the artifact. `None` for directory artifacts such as the macOS app bundle.
+  sha256: Option<String>,
+  size: Option<u64>,
+}
+
+#[derive(Debug, Serialize)]
+struct BuildOutput {
+  artifacts: Vec<Artifact>,
+}
+
 pub fn command(mut options: Options) -> Result<()> {
-  let (merge_config, merge_config_path) = if let Some(config) = &options.config {
-    if config.starts_with('{') {
-      (Some(config.to_string()), None)
-    } else {
-      (
-        Some(
-          std::fs::read_to_string(config).with_context(|| "failed to read custom configuration")?,
-        ),
-        Some(config.clone()),
-      )
-    }
-  } else {
-    (None, None)
-  };
-  options.config = merge_config;
+  // the file each merged configuration was read from, to point errors to it
+  let mut merge_config_sources = Vec::new();
+  options.config = options
+    .config
+    .iter()
+    .map(|config| {
+      if config.starts_with('{') {
+        merge_config_sources.push("--config".to_string());
+        Ok(config.to_string())
+      } else {
+        merge_config_sources.push(config.clone());
+        std::fs::read_to_string(config).with_context(|| "failed to read custom configuration")
+      }
+    })
+    .collect::<Result<_>>()?;
+
+  let output_format = options.format;
+  let mut artifacts = Vec::new();
 
   let tauri_path = tauri_dir();
   set_current_dir(&tauri_path).with_context(|| "failed to change current working directory")?;
 
-  let config = get_config(options.config.as_deref())?;
+  // `--profile` replaces `--debug`, so the build mode follows the selected profile
+  if let Some(profile) = &options.profile {
+    options.debug = is_debug_profile(profile)?;
+  }
+
+  let config = get_config(&options.config)?;
 
   let config_guard = config.lock().unwrap();
   let config_ = config_guard.as_ref().unwrap();
 
   let bundle_identifier_source = match config_.find_bundle_identifier_overwriter() {
-    Some(source) if source == MERGE_CONFIG_EXTENSION_NAME => merge_config_path.unwrap_or(source),
+    Some(source) if source == MERGE_CONFIG_EXTENSION_NAME => options
+      .config
+      .iter()
+      .zip(merge_config_sources)
+      .rev()
+      .find(|(config, _)| sets_bundle_identifier(config))
+      .map(|(_, source)| source)
+      .unwrap_or(source),
     Some(source) => source,
     None => "tauri.conf.json".into(),
   };
@@ -120,6 +224,8 @@ pub fn command(mut options: Options) -> Result<()> {
       before_build,
       &interface,
       options.debug,
+      output_format,
+      Default::default(),
     )?;
   }
 
@@ -214,10 +320,17 @@ pub fn command(mut options: Options) -> Result<()> {
           before_bundle,
           &interface,
           options.debug,
+          output_format,
+          Default::default(),
         )?;
       }
     }
 
+    let debug = options.debug;
+    let updater_base_url = options.updater_base_url.clone();
+    let updater_notes = options.updater_notes.clone();
+    let delta_from = options.delta_from.clone();
+
     let settings = app_settings
       .get_bundler_settings(&options.into(), config_, out_dir, package_types)
       .with_context(|| "failed to build bundler settings")?;
@@ -260,9 +373,31 @@ pub fn command(mut options: Options) -> Result<()> {
       if config_.tauri.bundle.appimage.bundle_media_framework {
         std::env::set_var("APPIMAGE_BUNDLE_GSTREAMER", "1");
       }
+      if let Some(update_information) = &config_.tauri.bundle.appimage.update_information {
+        std::env::set_var("APPIMAGE_UPDATE_INFORMATION", update_information);
+      }
     }
 
+    let arch = settings.binary_arch().to_string();
+    let target = settings.target().to_string();
+    let version = settings.version_string().to_string();
+    let bundle_dir = settings.project_out_directory().join("bundle");
+    let checksums = settings.checksums().clone();
     let bundles = bundle_project(settings).with_context(|| "failed to bundle project")?;
+    let mut release_paths: Vec<PathBuf> = bundles
+      .iter()
+      .flat_map(|bundle| bundle.bundle_paths.iter().cloned())
+      .collect();
+
+    let after_bundle = config_.build.after_bundle_command.clone();
+    let collect_artifacts = output_format == OutputFormat::Json || after_bundle.is_some();
+    if collect_artifacts {
+      for bundle in &bundles {
+        for path in &bundle.bundle_paths {
+          artifacts.push(artifact(path, bundle.package_type.short_name(), &arch)?);
+        }
+      }
+    }
 
     let updater_bundles: Vec<&Bundle> = bundles
       .iter()
@@ -306,18 +441,135 @@ pub fn command(mut options: Options) -> Result<()> {
               "The updater secret key from `TAURI_PRIVATE_KEY` does not match the public key defined in `tauri.conf.json > tauri > updater > pubkey`."
             ));
           }
+          if collect_artifacts {
+            artifacts.push(artifact(&signature_path, "signature", &arch)?);
+          }
+          release_paths.push(signature_path.clone());
           signed_paths.append(&mut vec![signature_path]);
         }
       }
 
+      let bundle_signature_paths = signed_paths.clone();
+      let patch = match (&delta_from, signed_paths.first()) {
+        (Some(old_bundle), Some(signature_path)) => {
+          // the bundle is next to its signature, as `<bundle>.sig`
+          let new_bundle = signature_path.with_extension("");
+          let patch = create_delta_patch(old_bundle, &new_bundle)?;
+          let (signature_path, _) = sign_file(&secret_key, &patch.path)?;
+          if collect_artifacts {
+            artifacts.push(artifact(&patch.path, "patch", &arch)?);
+            artifacts.push(artifact(&signature_path, "signature", &arch)?);
+          }
+          release_paths.push(patch.path.clone());
+          release_paths.push(signature_path.clone());
+          signed_paths.push(signature_path);
+          Some(patch)
+        }
+        _ => None,
+      };
+
       print_signed_updater_archive(&signed_paths)?;
+
+      if let Some(base_url) = updater_base_url {
+        let manifest_path = bundle_dir.join("latest.json");
+        write_updater_manifest(
+          &manifest_path,
+          &UpdaterRelease {
+            version: &version,
+            notes: updater_notes.as_deref().unwrap_or_default(),
+            base_url: &base_url,
+            target: &target,
+            arch: &arch,
+          },
+          &bundle_signature_paths,
+          patch.as_ref(),
+        )?;
+        info!("updater manifest at:");
+        info!("        {}", manifest_path.display());
+        if collect_artifacts {
+          artifacts.push(artifact(&manifest_path, "updater-manifest", &arch)?);
+        }
+        release_paths.push(manifest_path);
+      }
     }
+
+    // hashed last so the checksums also cover the updater signatures, patch and manifest
+    let checksum_paths = write_checksums(&checksums, &bundle_dir, &release_paths)?;
+    if !checksum_paths.is_empty() {
+      info!("checksums at:");
+      for path in &checksum_paths {
+        info!("        {}", path.display());
+        if collect_artifacts {
+          artifacts.push(artifact(path, "checksum", &arch)?);
+        }
+      }
+    }
+
+    // runs once the updater bundles are signed, so the hook sees every artifact
+    if let Some(after_bundle) = after_bundle {
+      let mut env = HashMap::new();
+      env.insert("TAURI_BUNDLE_ARTIFACTS", serde_json::to_string(&artifacts)?);
+      env.insert(
+        "TAURI_BUNDLE_CONFIG",
+        serde_json::to_string(&config_.tauri.bundle)?,
+      );
+      run_hook(
+        "afterBundleCommand",
+        after_bundle,
+        &interface,
+        debug,
+        output_format,
+        env,
+      )?;
+    }
+  }
+
+  if output_format == OutputFormat::Json {
+    println!(
+      "{}",
+      serde_json::to_string_pretty(&BuildOutput { artifacts })?
+    );
   }
 
   Ok(())
 }
 
-fn run_hook(name: &str, hook: HookCommand, interface: &AppInterface, debug: bool) -> Result<()> {
+/// Whether the configuration to merge sets `tauri > bundle > identifier`.
+fn sets_bundle_identifier(config: &str) -> bool {
+  serde_json::from_str::<serde_json::Value>(config)
+    .map(|config| config.pointer("/tauri/bundle/identifier").is_some())
+    .unwrap_or_default()
+}
+
+fn artifact(path: &Path, format: &'static str, arch: &str) -> Result<Artifact> {
+  let (sha256, size) = if path.is_file() {
+    let file =
+      File::open(path).with_context(|| format!("failed to open artifact {}", path.display()))?;
+    let size = file.metadata()?.len();
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut BufReader::new(file), &mut hasher)
+      .with_context(|| format!("failed to hash artifact {}", path.display()))?;
+    (Some(hex::encode(hasher.finalize())), Some(size))
+  } else {
+    (None, None)
+  };
+  Ok(Artifact {
+    path: path.to_path_buf(),
+    format,
+    arch: arch.into(),
+    sha256,
+    size,
+  })
+}
+
+fn run_hook(
+  name: &str,
+  hook: HookCommand,
+  interface: &AppInterface,
+  debug: bool,
+  output_format: OutputFormat,
+  extra_env: HashMap<&'static str, String>,
+) -> Result<()> {
   let (script, script_cwd) = match hook {
     HookCommand::Script(s) if s.is_empty() => (None, None),
     HookCommand::Script(s) => (Some(s), None),
@@ -329,26 +581,34 @@ fn run_hook(name: &str, hook: HookCommand, interface: &AppInterface, debug: bool
 
     let mut env = command_env(debug);
     env.extend(interface.env());
+    env.extend(extra_env);
 
     debug!("Setting environment for hook {:?}", env);
 
     #[cfg(target_os = "windows")]
-    let status = Command::new("cmd")
-      .arg("/S")
-      .arg("/C")
-      .arg(&script)
-      .current_dir(cwd)
-      .envs(env)
-      .piped()
-      .with_context(|| format!("failed to run `{}` with `cmd /C`", script))?;
+    let (mut command, shell) = {
+      let mut command = Command::new("cmd");
+      command.arg("/S").arg("/C");
+      (command, "cmd /C")
+    };
     #[cfg(not(target_os = "windows"))]
-    let status = Command::new("sh")
-      .arg("-c")
-      .arg(&script)
-      .current_dir(cwd)
-      .envs(env)
-      .piped()
-      .with_context(|| format!("failed to run `{}` with `sh -c`", script))?;
+    let (mut command, shell) = {
+      let mut command = Command::new("sh");
+      command.arg("-c");
+      (command, "sh -c")
+    };
+    command.arg(&script).current_dir(cwd).envs(env);
+
+    let status = if output_format == OutputFormat::Json {
+      // stdout is reserved for the JSON document listing the artifacts
+      command
+        .stdout(os_pipe::dup_stderr()?)
+        .stderr(os_pipe::dup_stderr()?)
+        .status()
+    } else {
+      command.piped()
+    }
+    .with_context(|| format!("failed to run `{}` with `{}`", script, shell))?;
 
     if !status.success() {
       bail!(
@@ -377,6 +637,135 @@ fn print_signed_updater_archive(output_paths: &[PathBuf]) -> crate::Result<()> {
   Ok(())
 }
 
+/// A binary patch between the previous and the new updater bundle.
+struct DeltaPatch {
+  path: PathBuf,
+  /// The SHA256 hash of the bundle the patch applies to.
+  from_sha256: String,
+}
+
+/// Creates a zstd compressed bsdiff patch from `old_bundle` to `new_bundle`, written next to the new bundle.
+fn create_delta_patch(old_bundle: &Path, new_bundle: &Path) -> crate::Result<DeltaPatch> {
+  let old = std::fs::read(old_bundle)
+    .with_context(|| format!("failed to read previous bundle {}", old_bundle.display()))?;
+  let new = std::fs::read(new_bundle)
+    .with_context(|| format!("failed to read updater bundle {}", new_bundle.display()))?;
+
+  let mut extension = new_bundle.extension().unwrap_or_default().to_os_string();
+  extension.push(".patch");
+  let path = new_bundle.with_extension(extension);
+
+  info!(action = "Diffing"; "{} against {}", new_bundle.display(), old_bundle.display());
+
+  let mut patch = Vec::new();
+  bsdiff::diff::diff(&old, &new, &mut patch)?;
+  let patch = zstd::encode_all(patch.as_slice(), zstd::DEFAULT_COMPRESSION_LEVEL)?;
+  std::fs::write(&path, patch).with_context(|| format!("failed to write {}", path.display()))?;
+
+  Ok(DeltaPatch {
+    path,
+    from_sha256: hex::encode(Sha256::digest(&old)),
+  })
+}
+
+/// The release described by the updater manifest.
+struct UpdaterRelease<'a> {
+  version: &'a str,
+  notes: &'a str,
+  /// The URL the bundles are uploaded to.
+  base_url: &'a str,
+  target: &'a str,
+  arch: &'a str,
+}
+
+/// Writes the manifest expected by the updater from a static JSON endpoint.
+///
+/// `signature_paths` are the `.sig` files written next to the updater bundles.
+fn write_updater_manifest(
+  manifest_path: &Path,
+  release: &UpdaterRelease,
+  signature_paths: &[PathBuf],
+  patch: Option<&DeltaPatch>,
+) -> crate::Result<()> {
+  let UpdaterRelease {
+    version,
+    notes,
+    base_url,
+    target,
+    arch,
+  } = *release;
+  let os = if target.contains("apple-darwin") {
+    "darwin"
+  } else if target.contains("windows") {
+    "windows"
+  } else if target.contains("linux") {
+    "linux"
+  } else {
+    bail!(
+      "the updater manifest can't be generated for the `{}` target",
+      target
+    );
+  };
+  // the architecture names used by the updater
+  let archs: &[&str] = match arch {
+    "x86" => &["i686"],
+    "arm" => &["armv7"],
+    "universal" => &["x86_64", "aarch64"],
+    other => &[other],
+  };
+
+  // the manifest lists a single bundle per platform,
+  // but on Windows both the MSI and NSIS updater bundles can be produced
+  if signature_paths.len() > 1 {
+    bail!(
+      "the updater manifest lists a single bundle per platform, but {} updater bundles were produced; restrict `tauri.conf.json > tauri > bundle > targets` to a single installer",
+      signature_paths.len()
+    );
+  }
+
+  let mut platforms = serde_json::Map::new();
+  if let Some(signature_path) = signature_paths.first() {
+    let signature = std::fs::read_to_string(signature_path)
+      .with_context(|| format!("failed to read signature {}", signature_path.display()))?;
+    // the signature is stored next to the bundle, as `<bundle>.sig`
+    let bundle_name = signature_path
+      .file_stem()
+      .expect("failed to get bundle file name")
+      .to_string_lossy();
+    let url = format!("{}/{}", base_url.trim_end_matches('/'), bundle_name);
+    let mut platform = serde_json::json!({ "signature": signature, "url": url });
+    if let Some(patch) = patch {
+      let mut signature_path = patch.path.clone().into_os_string();
+      signature_path.push(".sig");
+      let signature = std::fs::read_to_string(&signature_path)
+        .with_context(|| format!("failed to read signature {}", patch.path.display()))?;
+      platform["patch"] = serde_json::json!({
+        "from": patch.from_sha256,
+        "signature": signature,
+        "url": format!(
+          "{}/{}",
+          base_url.trim_end_matches('/'),
+          patch.path.file_name().expect("failed to get patch file name").to_string_lossy()
+        ),
+      });
+    }
+    for arch in archs {
+      platforms.insert(format!("{}-{}", os, arch), platform.clone());
+    }
+  }
+
+  let manifest = serde_json::json!({
+    "version": version,
+    "notes": notes,
+    "pub_date": time::OffsetDateTime::now_utc()
+      .format(&time::format_description::well_known::Rfc3339)?,
+    "platforms": platforms,
+  });
+  std::fs::write(manifest_path, serde_json::to_string_pretty(&manifest)?)
+    .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+  Ok(())
+}
+
 #[cfg(target_os = "linux")]
 mod pkgconfig_utils {
   use std::{path::PathBuf, process::Command};
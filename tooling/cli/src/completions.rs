@@ -0,0 +1,23 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use clap::{Command, Parser};
+use clap_complete::{generate, Shell};
+
+use std::io::stdout;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Shell completions")]
+pub struct Options {
+  /// Shell to generate the completion script for.
+  #[clap(short, long, value_enum)]
+  shell: Shell,
+}
+
+pub fn command(options: Options, mut cmd: Command) -> Result<()> {
+  let bin_name = cmd.get_bin_name().unwrap_or("cargo-tauri").to_string();
+  generate(options.shell, &mut cmd, bin_name, &mut stdout());
+  Ok(())
+}
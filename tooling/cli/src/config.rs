@@ -0,0 +1,36 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use clap::{Parser, Subcommand};
+
+mod print;
+mod schema;
+
+#[derive(Parser)]
+#[clap(
+  author,
+  version,
+  about = "Inspect the Tauri configuration",
+  subcommand_required(true),
+  arg_required_else_help(true)
+)]
+pub struct Cli {
+  #[clap(subcommand)]
+  command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  Print(print::Options),
+  Schema(schema::Options),
+}
+
+pub fn command(cli: Cli) -> Result<()> {
+  match cli.command {
+    Commands::Print(options) => print::command(options)?,
+    Commands::Schema(options) => schema::command(options)?,
+  }
+  Ok(())
+}
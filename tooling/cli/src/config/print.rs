@@ -0,0 +1,48 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{helpers::config::get as get_config, Result};
+use anyhow::Context;
+use clap::{ArgAction, Parser};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Print the configuration after merging and resolving every source")]
+pub struct Options {
+  /// Dot separated path of the value to print, e.g. `tauri.bundle.identifier`
+  key: Option<String>,
+  /// JSON string or path to JSON file to merge with tauri.conf.json, as in `tauri build`
+  #[clap(short, long, action = ArgAction::Append)]
+  config: Vec<String>,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let merge_configs = options
+    .config
+    .iter()
+    .map(|config| {
+      if config.starts_with('{') {
+        Ok(config.to_string())
+      } else {
+        std::fs::read_to_string(config).with_context(|| "failed to read custom configuration")
+      }
+    })
+    .collect::<Result<Vec<_>>>()?;
+
+  let config = get_config(&merge_configs)?;
+  let config_guard = config.lock().unwrap();
+  let config = serde_json::to_value(&**config_guard.as_ref().unwrap())?;
+
+  let value = match &options.key {
+    Some(key) => {
+      let pointer = format!("/{}", key.replace('.', "/"));
+      config
+        .pointer(&pointer)
+        .with_context(|| format!("`{}` is not set in the configuration", key))?
+    }
+    None => &config,
+  };
+  println!("{}", serde_json::to_string_pretty(value)?);
+
+  Ok(())
+}
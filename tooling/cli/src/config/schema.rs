@@ -0,0 +1,29 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use anyhow::Context;
+use clap::Parser;
+use std::path::PathBuf;
+
+/// The JSON schema of the configuration, generated from the `tauri-utils` config structs.
+const SCHEMA: &str = include_str!("../../schema.json");
+
+#[derive(Debug, Parser)]
+#[clap(about = "Print the JSON schema of the configuration file")]
+pub struct Options {
+  /// Write the schema to a file instead of stdout
+  #[clap(short, long)]
+  output: Option<PathBuf>,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  if let Some(output) = options.output {
+    std::fs::write(&output, SCHEMA)
+      .with_context(|| format!("failed to write schema to {}", output.display()))?;
+  } else {
+    print!("{}", SCHEMA);
+  }
+  Ok(())
+}
@@ -0,0 +1,204 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{
+  helpers::app_paths::{app_dir, tauri_dir},
+  info::{
+    app_package_manager, crate_latest_version, cross_command, get_version, npm_latest_version,
+    npm_package_version, CargoLock, PackageManager,
+  },
+  interface::rust::get_workspace_dir,
+  CommandExt, Result,
+};
+use anyhow::bail;
+use clap::{Parser, Subcommand};
+use log::{info, warn};
+use semver::Version;
+
+use std::{fs::read_to_string, process::Command};
+
+const RUST_DEPENDENCIES: &[&str] = &["tauri", "tauri-build"];
+/// The NPM packages and whether they are development dependencies.
+const NPM_DEPENDENCIES: &[(&str, bool)] = &[("@tauri-apps/api", false), ("@tauri-apps/cli", true)];
+
+#[derive(Parser)]
+#[clap(
+  author,
+  version,
+  about = "Check and update the Tauri dependencies",
+  subcommand_required(true),
+  arg_required_else_help(true)
+)]
+pub struct Cli {
+  #[clap(subcommand)]
+  command: Commands,
+}
+
+#[derive(Subcommand)]
+enum Commands {
+  /// Report the Tauri dependencies that are outdated
+  Check,
+  /// Update the outdated Tauri dependencies
+  Update,
+}
+
+enum Ecosystem {
+  Rust,
+  Npm { dev: bool },
+}
+
+struct Dependency {
+  name: &'static str,
+  ecosystem: Ecosystem,
+  current: String,
+  latest: Option<String>,
+}
+
+impl Dependency {
+  fn is_outdated(&self) -> bool {
+    match (
+      Version::parse(&self.current),
+      self.latest.as_deref().map(Version::parse),
+    ) {
+      (Ok(current), Some(Ok(latest))) => current < latest,
+      _ => false,
+    }
+  }
+}
+
+pub fn command(cli: Cli) -> Result<()> {
+  let (dependencies, package_manager) = dependencies()?;
+
+  for dependency in &dependencies {
+    let ecosystem = match dependency.ecosystem {
+      Ecosystem::Rust => "RUST",
+      Ecosystem::Npm { .. } => "NPM",
+    };
+    if dependency.is_outdated() {
+      warn!(
+        "{} [{}] {} is outdated, latest: {}",
+        dependency.name,
+        ecosystem,
+        dependency.current,
+        dependency.latest.as_deref().unwrap_or_default()
+      );
+    } else {
+      info!(
+        "{} [{}] {} is up to date",
+        dependency.name, ecosystem, dependency.current
+      );
+    }
+  }
+
+  if let Commands::Update = cli.command {
+    update(&dependencies, &package_manager)?;
+  }
+
+  Ok(())
+}
+
+/// Finds the installed versions of the Tauri dependencies and their latest published versions.
+fn dependencies() -> Result<(Vec<Dependency>, PackageManager)> {
+  let mut dependencies = Vec::new();
+
+  let lock: Option<CargoLock> = get_workspace_dir()
+    .ok()
+    .and_then(|p| read_to_string(p.join("Cargo.lock")).ok())
+    .and_then(|s| toml::from_str(&s).ok());
+  if let Some(lock) = lock {
+    for name in RUST_DEPENDENCIES {
+      if let Some(current) = lock
+        .package
+        .iter()
+        .filter(|p| p.name == *name)
+        .filter_map(|p| Version::parse(&p.version).ok())
+        .max()
+      {
+        dependencies.push(Dependency {
+          name,
+          ecosystem: Ecosystem::Rust,
+          current: current.to_string(),
+          latest: crate_latest_version(name),
+        });
+      }
+    }
+  } else {
+    warn!("Cargo.lock not found, build the app to check the Rust dependencies");
+  }
+
+  let app_dir = app_dir();
+  let package_manager = if app_dir.join("package.json").exists() {
+    let yarn_version = get_version("yarn", &[])
+      .unwrap_or_default()
+      .unwrap_or_default();
+    let package_manager = app_package_manager(app_dir, &yarn_version)?;
+    for (name, dev) in NPM_DEPENDENCIES {
+      if let Some(current) = npm_package_version(&package_manager, name, app_dir)? {
+        dependencies.push(Dependency {
+          name,
+          ecosystem: Ecosystem::Npm { dev: *dev },
+          current,
+          latest: npm_latest_version(&package_manager, name)?,
+        });
+      }
+    }
+    package_manager
+  } else {
+    PackageManager::Npm
+  };
+
+  Ok((dependencies, package_manager))
+}
+
+fn update(dependencies: &[Dependency], package_manager: &PackageManager) -> Result<()> {
+  let outdated = dependencies
+    .iter()
+    .filter(|d| d.is_outdated())
+    .collect::<Vec<_>>();
+  if outdated.is_empty() {
+    info!("All Tauri dependencies are up to date.");
+    return Ok(());
+  }
+
+  let rust_outdated = outdated
+    .iter()
+    .filter(|d| matches!(d.ecosystem, Ecosystem::Rust))
+    .collect::<Vec<_>>();
+  if !rust_outdated.is_empty() {
+    let mut cargo = Command::new("cargo");
+    cargo.arg("update").current_dir(tauri_dir());
+    for dependency in &rust_outdated {
+      cargo.arg("-p").arg(dependency.name);
+    }
+    info!(action = "Running"; "cargo update");
+    if !cargo.piped()?.success() {
+      bail!("failed to update the Rust dependencies");
+    }
+    info!(
+      "The Rust dependencies are updated within their version requirements, run `tauri migrate` to update the requirements."
+    );
+  }
+
+  for dependency in outdated {
+    if let Ecosystem::Npm { dev } = dependency.ecosystem {
+      let (bin, subcommand, dev_flag) = match package_manager {
+        PackageManager::Npm => ("npm", "install", "--save-dev"),
+        PackageManager::Pnpm => ("pnpm", "add", "--save-dev"),
+        PackageManager::Yarn | PackageManager::Berry => ("yarn", "add", "--dev"),
+      };
+      let mut cmd = cross_command(bin);
+      cmd.arg(subcommand).current_dir(app_dir());
+      if dev {
+        cmd.arg(dev_flag);
+      }
+      cmd.arg(format!("{}@latest", dependency.name));
+      info!(action = "Running"; "{} {} {}@latest", bin, subcommand, dependency.name);
+      if !cmd.piped()?.success() {
+        bail!("failed to update {}", dependency.name);
+      }
+    }
+  }
+
+  Ok(())
+}
@@ -8,7 +8,7 @@ use crate::{
     command_env,
     config::{get as get_config, reload as reload_config, AppUrl, BeforeDevCommand, WindowUrl},
   },
-  interface::{AppInterface, ExitReason, Interface},
+  interface::{rust::is_debug_profile, AppInterface, ExitReason, Interface},
   CommandExt, Result,
 };
 use clap::{ArgAction, Parser};
@@ -47,12 +47,22 @@ pub struct Options {
   /// List of cargo features to activate
   #[clap(short, long, action = ArgAction::Append, num_args(0..))]
   pub features: Option<Vec<String>>,
+  /// Do not activate the `default` feature
+  #[clap(long)]
+  pub no_default_features: bool,
+  /// Cargo profile to build with, instead of `dev` (or `release` with `--release`)
+  ///
+  /// The app runs in release mode if the profile is `release`, `bench` or a custom profile that inherits from them.
+  #[clap(long, conflicts_with = "release_mode")]
+  pub profile: Option<String>,
   /// Exit on panic
   #[clap(short, long)]
   exit_on_panic: bool,
-  /// JSON string or path to JSON file to merge with tauri.conf.json
-  #[clap(short, long)]
-  pub config: Option<String>,
+  /// JSON string or path to JSON file to merge with tauri.conf.json.
+  ///
+  /// Can be specified multiple times, the values are merged in order.
+  #[clap(short, long, action = ArgAction::Append)]
+  pub config: Vec<String>,
   /// Run the code in release mode
   #[clap(long = "release")]
   pub release_mode: bool,
@@ -75,19 +85,26 @@ pub fn command(options: Options) -> Result<()> {
 
 fn command_internal(mut options: Options) -> Result<()> {
   let tauri_path = tauri_dir();
-  options.config = if let Some(config) = &options.config {
-    Some(if config.starts_with('{') {
-      config.to_string()
-    } else {
-      std::fs::read_to_string(config).with_context(|| "failed to read custom configuration")?
+  options.config = options
+    .config
+    .iter()
+    .map(|config| {
+      if config.starts_with('{') {
+        Ok(config.to_string())
+      } else {
+        std::fs::read_to_string(config).with_context(|| "failed to read custom configuration")
+      }
     })
-  } else {
-    None
-  };
+    .collect::<Result<_>>()?;
 
   set_current_dir(&tauri_path).with_context(|| "failed to change current working directory")?;
 
-  let config = get_config(options.config.as_deref())?;
+  // `--profile` replaces `--release`, so the build mode follows the selected profile
+  if let Some(profile) = &options.profile {
+    options.release_mode = !is_debug_profile(profile)?;
+  }
+
+  let config = get_config(&options.config)?;
 
   let mut interface = AppInterface::new(
     config.lock().unwrap().as_ref().unwrap(),
@@ -229,20 +246,14 @@ fn command_internal(mut options: Options) -> Result<()> {
       // or better separate the config passed from the cli internally and
       // config passed by the user in `--config` into to separate env vars
       // and the context merges, the user first, then the internal cli config
-      if let Some(c) = options.config {
-        let mut c: tauri_utils::config::Config = serde_json::from_str(&c)?;
-        c.build.dev_path = dev_path.clone();
-        options.config = Some(serde_json::to_string(&c).unwrap());
-      } else {
-        options.config = Some(format!(
-          r#"{{ "build": {{ "devPath": "{}" }} }}"#,
-          SERVER_URL
-        ))
-      }
+      options.config.push(format!(
+        r#"{{ "build": {{ "devPath": "{}" }} }}"#,
+        SERVER_URL
+      ));
     }
   }
 
-  reload_config(options.config.as_deref())?;
+  reload_config(&options.config)?;
 
   if std::env::var_os("TAURI_SKIP_DEVSERVER_CHECK") != Some("true".into()) {
     if let AppUrl::Url(WindowUrl::External(dev_server_url)) = dev_path {
@@ -10,7 +10,7 @@ use std::{
 };
 
 use ignore::WalkBuilder;
-use once_cell::sync::Lazy;
+use once_cell::sync::{Lazy, OnceCell};
 
 use tauri_utils::config::parse::{
   folder_has_configuration_file, is_configuration_file, ConfigFormat,
@@ -18,6 +18,47 @@ use tauri_utils::config::parse::{
 
 const TAURI_GITIGNORE: &[u8] = include_bytes!("../../tauri.gitignore");
 
+static PROJECT_PATH: OnceCell<PathBuf> = OnceCell::new();
+
+/// Sets the directory used to find the Tauri project instead of the current working directory.
+pub fn set_project_path(path: PathBuf) {
+  let path = if path.is_relative() {
+    current_dir().expect("failed to read cwd").join(path)
+  } else {
+    path
+  };
+  let _ = PROJECT_PATH.set(path);
+}
+
+/// The directory where the Tauri project is searched for.
+///
+/// It is the `--project-path` value if set, or the `project-path` of the `[package.metadata.tauri]`
+/// or `[workspace.metadata.tauri]` table of the closest Cargo manifest declaring it, or the current directory.
+fn project_root() -> PathBuf {
+  if let Some(path) = PROJECT_PATH.get() {
+    return path.clone();
+  }
+  let cwd = current_dir().expect("failed to read cwd");
+  cwd
+    .ancestors()
+    .find_map(cargo_metadata_project_path)
+    .unwrap_or(cwd)
+}
+
+fn cargo_metadata_project_path(dir: &Path) -> Option<PathBuf> {
+  let manifest = std::fs::read_to_string(dir.join("Cargo.toml")).ok()?;
+  let manifest: toml::Value = toml::from_str(&manifest).ok()?;
+  ["package", "workspace"].iter().find_map(|table| {
+    manifest
+      .get(table)?
+      .get("metadata")?
+      .get("tauri")?
+      .get("project-path")?
+      .as_str()
+      .map(|path| dir.join(path))
+  })
+}
+
 fn lookup<F: Fn(&PathBuf) -> bool>(dir: &Path, checker: F) -> Option<PathBuf> {
   let mut default_gitignore = std::env::temp_dir();
   default_gitignore.push(".gitignore");
@@ -59,17 +100,32 @@ fn lookup<F: Fn(&PathBuf) -> bool>(dir: &Path, checker: F) -> Option<PathBuf> {
   None
 }
 
+/// Looks for the Tauri project in the parent folders,
+/// since the CLI may be running from a subfolder of the project, e.g. `src-tauri/src`.
+fn lookup_ancestors(dir: &Path) -> Option<PathBuf> {
+  dir.ancestors().skip(1).find_map(|dir| {
+    if folder_has_configuration_file(dir) {
+      Some(dir.to_path_buf())
+    } else if folder_has_configuration_file(&dir.join("src-tauri")) {
+      Some(dir.join("src-tauri"))
+    } else {
+      None
+    }
+  })
+}
+
 fn get_tauri_dir() -> PathBuf {
-  let cwd = current_dir().expect("failed to read cwd");
+  let root = project_root();
 
-  if cwd.join("src-tauri/tauri.conf.json").exists()
-    || cwd.join("src-tauri/tauri.conf.json5").exists()
+  if root.join("src-tauri/tauri.conf.json").exists()
+    || root.join("src-tauri/tauri.conf.json5").exists()
   {
-    return cwd.join("src-tauri/");
+    return root.join("src-tauri/");
   }
 
-  lookup(&cwd, |path| folder_has_configuration_file(path) || is_configuration_file(path))
+  lookup(&root, |path| folder_has_configuration_file(path) || is_configuration_file(path))
   .map(|p| if p.is_dir() { p } else {  p.parent().unwrap().to_path_buf() })
+  .or_else(|| lookup_ancestors(&root))
   .unwrap_or_else(||
     panic!("Couldn't recognize the current folder as a Tauri project. It must contain a `{}`, `{}` or `{}` file in any subfolder.",
       ConfigFormat::Json.into_file_name(),
@@ -80,7 +136,7 @@ fn get_tauri_dir() -> PathBuf {
 }
 
 fn get_app_dir() -> Option<PathBuf> {
-  lookup(&current_dir().expect("failed to read cwd"), |path| {
+  lookup(&project_root(), |path| {
     if let Some(file_name) = path.file_name() {
       file_name == OsStr::new("package.json")
     } else {
@@ -14,6 +14,7 @@ use std::{
   collections::HashMap,
   env::{set_var, var_os},
   ffi::OsStr,
+  path::Path,
   process::exit,
   sync::{Arc, Mutex},
 };
@@ -93,17 +94,43 @@ pub fn wix_settings(config: WixConfig) -> tauri_bundler::WixSettings {
     enable_elevated_update_task: config.enable_elevated_update_task,
     banner_path: config.banner_path,
     dialog_image_path: config.dialog_image_path,
+    upgrade_code: config.upgrade_code,
+    bundle_vc_redist: config.bundle_vc_redist,
+    shortcut_name: config.shortcut_name,
+    start_menu_folder: config.start_menu_folder,
+    skip_desktop_shortcut: config.skip_desktop_shortcut,
+    install_dir: config.install_dir,
     fips_compliant: var_os("TAURI_FIPS_COMPLIANT").map_or(false, |v| v == "true"),
   }
 }
 
+pub fn nsis_settings(config: NsisConfig) -> tauri_bundler::NsisSettings {
+  tauri_bundler::NsisSettings {
+    template: config.template,
+    license: config.license,
+    header_image: config.header_image,
+    sidebar_image: config.sidebar_image,
+    installer_icon: config.installer_icon,
+  }
+}
+
+pub fn msix_settings(config: MsixConfig) -> tauri_bundler::MsixSettings {
+  tauri_bundler::MsixSettings {
+    identity_name: config.identity_name,
+    publisher: config.publisher,
+    publisher_display_name: config.publisher_display_name,
+    capabilities: config.capabilities,
+    template: config.template,
+  }
+}
+
 fn config_handle() -> &'static ConfigHandle {
   static CONFING_HANDLE: Lazy<ConfigHandle> = Lazy::new(Default::default);
   &CONFING_HANDLE
 }
 
 /// Gets the static parsed config from `tauri.conf.json`.
-fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<ConfigHandle> {
+fn get_internal(merge_configs: &[String], reload: bool) -> crate::Result<ConfigHandle> {
   if !reload && config_handle().lock().unwrap().is_some() {
     return Ok(config_handle().clone());
   }
@@ -115,7 +142,7 @@ fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<Confi
   let mut extensions = HashMap::new();
 
   if let Some((platform_config, config_path)) =
-    tauri_utils::config::parse::read_platform(tauri_dir)?
+    tauri_utils::config::parse::read_platform(tauri_dir.clone())?
   {
     merge(&mut config, &platform_config);
     extensions.insert(
@@ -124,14 +151,21 @@ fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<Confi
     );
   }
 
-  if let Some(merge_config) = merge_config {
-    set_var("TAURI_CONFIG", merge_config);
-    let merge_config: JsonValue =
-      serde_json::from_str(merge_config).with_context(|| "failed to parse config to merge")?;
-    merge(&mut config, &merge_config);
+  if !merge_configs.is_empty() {
+    let mut merge_config = JsonValue::Object(Default::default());
+    for config_to_merge in merge_configs {
+      let config_to_merge: JsonValue =
+        serde_json::from_str(config_to_merge).with_context(|| "failed to parse config to merge")?;
+      merge(&mut config, &config_to_merge);
+      merge(&mut merge_config, &config_to_merge);
+    }
+    set_var("TAURI_CONFIG", serde_json::to_string(&merge_config)?);
     extensions.insert(MERGE_CONFIG_EXTENSION_NAME.into(), merge_config);
   };
 
+  let package_version = cargo_package_version(&tauri_dir);
+  tauri_utils::config::parse::substitute_variables(&mut config, package_version.as_deref())?;
+
   if config_path.extension() == Some(OsStr::new("json"))
     || config_path.extension() == Some(OsStr::new("json5"))
   {
@@ -163,10 +197,23 @@ fn get_internal(merge_config: Option<&str>, reload: bool) -> crate::Result<Confi
   Ok(config_handle().clone())
 }
 
-pub fn get(merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
-  get_internal(merge_config, false)
+/// Reads the package version from the Cargo manifest, used to substitute `${version}` in the config.
+fn cargo_package_version(tauri_dir: &Path) -> Option<String> {
+  let manifest = std::fs::read_to_string(tauri_dir.join("Cargo.toml")).ok()?;
+  let manifest: toml::Value = toml::from_str(&manifest).ok()?;
+  match manifest.get("package")?.get("version")? {
+    toml::Value::String(version) => Some(version.clone()),
+    // `version.workspace = true`, let cargo resolve the inherited version
+    _ => crate::interface::rust::get_package_version(tauri_dir)
+      .ok()
+      .flatten(),
+  }
+}
+
+pub fn get(merge_configs: &[String]) -> crate::Result<ConfigHandle> {
+  get_internal(merge_configs, false)
 }
 
-pub fn reload(merge_config: Option<&str>) -> crate::Result<ConfigHandle> {
-  get_internal(merge_config, true)
+pub fn reload(merge_configs: &[String]) -> crate::Result<ConfigHandle> {
+  get_internal(merge_configs, true)
 }
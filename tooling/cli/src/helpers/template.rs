@@ -4,13 +4,13 @@
 
 use std::{
   collections::BTreeMap,
-  fs::{create_dir_all, File},
-  io::Write,
+  fs::{create_dir_all, read, write},
   path::Path,
 };
 
 use handlebars::Handlebars;
 use include_dir::Dir;
+use similar::TextDiff;
 
 pub fn render<P: AsRef<Path>>(
   handlebars: &Handlebars<'_>,
@@ -18,7 +18,69 @@ pub fn render<P: AsRef<Path>>(
   dir: &Dir<'_>,
   out_dir: P,
 ) -> crate::Result<()> {
-  create_dir_all(out_dir.as_ref().join(dir.path()))?;
+  render_with(
+    handlebars,
+    data,
+    dir,
+    out_dir.as_ref(),
+    &mut |path, contents| {
+      if let Some(parent) = path.parent() {
+        create_dir_all(parent)?;
+      }
+      write(path, contents)?;
+      Ok(())
+    },
+  )
+}
+
+/// Renders the templates without writing them, printing a unified diff against the files on disk.
+pub fn render_diff<P: AsRef<Path>>(
+  handlebars: &Handlebars<'_>,
+  data: &BTreeMap<&str, serde_json::Value>,
+  dir: &Dir<'_>,
+  out_dir: P,
+) -> crate::Result<()> {
+  render_with(
+    handlebars,
+    data,
+    dir,
+    out_dir.as_ref(),
+    &mut |path, contents| {
+      let current = read(path).ok();
+      if current.as_deref() == Some(contents.as_slice()) {
+        return Ok(());
+      }
+      let path_str = path.display().to_string();
+      let current_header = if current.is_some() {
+        path_str.as_str()
+      } else {
+        "/dev/null"
+      };
+      let current = current.unwrap_or_default();
+      match (
+        std::str::from_utf8(&current),
+        std::str::from_utf8(&contents),
+      ) {
+        (Ok(current), Ok(contents)) => print!(
+          "{}",
+          TextDiff::from_lines(current, contents)
+            .unified_diff()
+            .header(current_header, &path_str)
+        ),
+        _ => println!("Binary file {} differs", path_str),
+      }
+      Ok(())
+    },
+  )
+}
+
+fn render_with(
+  handlebars: &Handlebars<'_>,
+  data: &BTreeMap<&str, serde_json::Value>,
+  dir: &Dir<'_>,
+  out_dir: &Path,
+  output: &mut dyn FnMut(&Path, Vec<u8>) -> crate::Result<()>,
+) -> crate::Result<()> {
   for file in dir.files() {
     let mut file_path = file.path().to_path_buf();
     // cargo for some reason ignores the /templates folder packaging when it has a Cargo.toml file inside
@@ -28,17 +90,18 @@ pub fn render<P: AsRef<Path>>(
         file_path.set_extension("toml");
       }
     }
-    let mut output_file = File::create(out_dir.as_ref().join(file_path))?;
-    if let Some(utf8) = file.contents_utf8() {
+    let contents = if let Some(utf8) = file.contents_utf8() {
       handlebars
-        .render_template_to_write(utf8, &data, &mut output_file)
-        .expect("Failed to render template");
+        .render_template(utf8, &data)
+        .expect("Failed to render template")
+        .into_bytes()
     } else {
-      output_file.write_all(file.contents())?;
-    }
+      file.contents().to_vec()
+    };
+    output(&out_dir.join(file_path), contents)?;
   }
   for dir in dir.dirs() {
-    render(handlebars, data, dir, out_dir.as_ref())?;
+    render_with(handlebars, data, dir, out_dir, output)?;
   }
   Ok(())
 }
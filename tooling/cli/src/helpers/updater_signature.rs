@@ -4,7 +4,9 @@
 
 use anyhow::Context;
 use base64::{decode, encode};
-use minisign::{sign, KeyPair as KP, SecretKey, SecretKeyBox, SignatureBox};
+use minisign::{
+  sign, verify, KeyPair as KP, PublicKey, PublicKeyBox, SecretKey, SecretKeyBox, SignatureBox,
+};
 use std::{
   fs::{self, File, OpenOptions},
   io::{BufReader, BufWriter, Write},
@@ -145,6 +147,30 @@ pub fn secret_key(private_key: String, password: Option<String>) -> crate::Resul
   Ok(sk)
 }
 
+/// Gets the updater public key from its base64 encoded form.
+pub fn public_key(public_key: String) -> crate::Result<PublicKey> {
+  let decoded_public = decode_key(public_key)?;
+  let pk_box = PublicKeyBox::from_string(&decoded_public)
+    .with_context(|| "failed to load updater public key")?;
+  let pk = pk_box
+    .into_public_key()
+    .with_context(|| "failed to load updater public key")?;
+  Ok(pk)
+}
+
+/// Verifies the base64 encoded signature of a file, as written by [`sign_file`].
+pub fn verify_file<P>(public_key: &PublicKey, bin_path: P, signature: String) -> crate::Result<()>
+where
+  P: AsRef<Path>,
+{
+  let signature_box = SignatureBox::from_string(&decode_key(signature)?)
+    .with_context(|| "failed to load signature")?;
+  let data_reader = open_data_file(bin_path)?;
+  verify(public_key, &signature_box, data_reader, true, false, false)
+    .with_context(|| "signature verification failed")?;
+  Ok(())
+}
+
 fn unix_timestamp() -> u64 {
   let start = SystemTime::now();
   let since_the_epoch = start
@@ -11,7 +11,7 @@ use std::{
   path::{Path, PathBuf},
 };
 
-use anyhow::Context;
+use anyhow::{bail, Context};
 use clap::Parser;
 use icns::{IconFamily, IconType};
 use image::{
@@ -20,7 +20,7 @@ use image::{
     png::{CompressionType, FilterType as PngFilterType, PngEncoder},
   },
   imageops::FilterType,
-  open, ColorType, DynamicImage, ImageEncoder,
+  open, ColorType, DynamicImage, ImageEncoder, RgbaImage,
 };
 use serde::Deserialize;
 
@@ -34,7 +34,7 @@ struct IcnsEntry {
 #[clap(about = "Generates various icons for all major platforms")]
 pub struct Options {
   // TODO: Confirm 1240px
-  /// Path to the source icon (png, 1240x1240px with transparency).
+  /// Path to the source icon (png, 1240x1240px with transparency, or svg).
   #[clap(default_value = "./app-icon.png")]
   input: PathBuf,
   /// Output directory.
@@ -50,14 +50,21 @@ pub fn command(options: Options) -> Result<()> {
 
   // Try to read the image as a DynamicImage, convert it to rgba8 and turn it into a DynamicImage again.
   // Both things should be catched by the explicit conversions to rgba8 anyway.
-  let source = open(input)
-    .context("Can't read and decode source image")?
-    .into_rgba8();
+  let source = if input
+    .extension()
+    .map_or(false, |ext| ext.eq_ignore_ascii_case("svg"))
+  {
+    render_svg(&input).context("Can't read and render source SVG")?
+  } else {
+    open(input)
+      .context("Can't read and decode source image")?
+      .into_rgba8()
+  };
 
   let source = DynamicImage::ImageRgba8(source);
 
   if source.height() != source.width() {
-    panic!("Source image must be square");
+    bail!("Source image must be square");
   }
 
   appx(&source, &out_dir).context("Failed to generate appx icons")?;
@@ -71,6 +78,39 @@ pub fn command(options: Options) -> Result<()> {
   Ok(())
 }
 
+/// Rasterizes an SVG icon to a 1024x1024 image, the largest size generated.
+fn render_svg(path: &Path) -> Result<RgbaImage> {
+  const SIZE: u32 = 1024;
+
+  let data = std::fs::read(path)?;
+  let options = usvg::Options {
+    resources_dir: path.parent().map(Path::to_path_buf),
+    ..Default::default()
+  };
+  let tree = usvg::Tree::from_data(&data, &options.to_ref())?;
+  let size = tree.svg_node().size;
+  if size.width() != size.height() {
+    bail!("Source image must be square");
+  }
+
+  let mut pixmap = tiny_skia::Pixmap::new(SIZE, SIZE).context("Can't allocate the SVG canvas")?;
+  resvg::render(
+    &tree,
+    usvg::FitTo::Size(SIZE, SIZE),
+    tiny_skia::Transform::default(),
+    pixmap.as_mut(),
+  )
+  .context("Can't render the SVG")?;
+
+  // the pixmap is premultiplied, `image` expects straight alpha
+  let mut pixels = Vec::with_capacity((SIZE * SIZE * 4) as usize);
+  for pixel in pixmap.pixels() {
+    let color = pixel.demultiply();
+    pixels.extend_from_slice(&[color.red(), color.green(), color.blue(), color.alpha()]);
+  }
+  RgbaImage::from_raw(SIZE, SIZE, pixels).context("Invalid SVG render size")
+}
+
 fn appx(source: &DynamicImage, out_dir: &Path) -> Result<()> {
   log::info!(action = "Appx"; "Creating StoreLogo.png");
   resize_and_save_png(source, 50, &out_dir.join("StoreLogo.png"))?;
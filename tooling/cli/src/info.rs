@@ -26,15 +26,15 @@ struct YarnVersionInfo {
 }
 
 #[derive(Clone, Deserialize)]
-struct CargoLockPackage {
-  name: String,
-  version: String,
+pub(crate) struct CargoLockPackage {
+  pub(crate) name: String,
+  pub(crate) version: String,
   source: Option<String>,
 }
 
 #[derive(Deserialize)]
-struct CargoLock {
-  package: Vec<CargoLockPackage>,
+pub(crate) struct CargoLock {
+  pub(crate) package: Vec<CargoLockPackage>,
 }
 
 #[derive(Deserialize)]
@@ -78,7 +78,7 @@ struct CargoManifest {
 }
 
 #[derive(Debug, PartialEq, Eq)]
-enum PackageManager {
+pub(crate) enum PackageManager {
   Npm,
   Pnpm,
   Yarn,
@@ -87,7 +87,11 @@ enum PackageManager {
 
 #[derive(Debug, Parser)]
 #[clap(about = "Shows information about Tauri dependencies and project configuration")]
-pub struct Options;
+pub struct Options {
+  /// Prints the information as a JSON document, for tooling.
+  #[clap(long)]
+  pub json: bool,
+}
 
 fn version_metadata() -> Result<VersionMetadata> {
   serde_json::from_str::<VersionMetadata>(include_str!("../metadata.json")).map_err(Into::into)
@@ -127,7 +131,7 @@ pub(crate) fn cli_upstream_version() -> Result<String> {
     .map_err(|e| anyhow::Error::new(e))
 }
 
-fn crate_latest_version(name: &str) -> Option<String> {
+pub(crate) fn crate_latest_version(name: &str) -> Option<String> {
   let url = format!("https://docs.rs/crate/{}/", name);
   match ureq::get(&url).call() {
     Ok(response) => match (response.status(), response.header("location")) {
@@ -139,7 +143,7 @@ fn crate_latest_version(name: &str) -> Option<String> {
 }
 
 #[allow(clippy::let_and_return)]
-fn cross_command(bin: &str) -> Command {
+pub(crate) fn cross_command(bin: &str) -> Command {
   #[cfg(target_os = "windows")]
   let cmd = {
     let mut cmd = Command::new("cmd");
@@ -151,7 +155,7 @@ fn cross_command(bin: &str) -> Command {
   cmd
 }
 
-fn npm_latest_version(pm: &PackageManager, name: &str) -> crate::Result<Option<String>> {
+pub(crate) fn npm_latest_version(pm: &PackageManager, name: &str) -> crate::Result<Option<String>> {
   match pm {
     PackageManager::Yarn => {
       let mut cmd = cross_command("yarn");
@@ -211,7 +215,7 @@ fn npm_latest_version(pm: &PackageManager, name: &str) -> crate::Result<Option<S
   }
 }
 
-fn npm_package_version<P: AsRef<Path>>(
+pub(crate) fn npm_package_version<P: AsRef<Path>>(
   pm: &PackageManager,
   name: &str,
   app_dir: P,
@@ -267,7 +271,7 @@ fn npm_package_version<P: AsRef<Path>>(
   }
 }
 
-fn get_version(command: &str, args: &[&str]) -> crate::Result<Option<String>> {
+pub(crate) fn get_version(command: &str, args: &[&str]) -> crate::Result<Option<String>> {
   let output = cross_command(command)
     .args(args)
     .arg("--version")
@@ -369,6 +373,37 @@ fn build_tools_version() -> crate::Result<Option<Vec<String>>> {
   })
 }
 
+fn installed_rust_targets() -> crate::Result<Option<Vec<String>>> {
+  let output = cross_command("rustup")
+    .args(["target", "list", "--installed"])
+    .output()?;
+  let targets = if output.status.success() {
+    Some(
+      String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .map(|target| target.trim().to_string())
+        .filter(|target| !target.is_empty())
+        .collect(),
+    )
+  } else {
+    None
+  };
+  Ok(targets)
+}
+
+#[cfg(target_os = "linux")]
+fn webkit2gtk_version() -> crate::Result<Option<String>> {
+  let output = Command::new("pkg-config")
+    .args(["--modversion", "webkit2gtk-4.0"])
+    .output()?;
+  let version = if output.status.success() {
+    Some(String::from_utf8_lossy(&output.stdout).replace(['\n', '\r'], ""))
+  } else {
+    None
+  };
+  Ok(version)
+}
+
 fn active_rust_toolchain() -> crate::Result<Option<String>> {
   let output = cross_command("rustup")
     .args(["show", "active-toolchain"])
@@ -519,9 +554,36 @@ fn indent(spaces: usize) {
   print!("{}", " ".repeat(spaces));
 }
 
+/// The information gathered by the command, printed as a JSON document with `--json`.
+#[derive(Default)]
+struct Report {
+  json: bool,
+  sections: serde_json::Map<String, serde_json::Value>,
+  section: String,
+}
+
+impl Report {
+  fn insert(&mut self, key: impl Into<String>, value: serde_json::Value) {
+    if let Some(section) = self
+      .sections
+      .get_mut(&self.section)
+      .and_then(|section| section.as_object_mut())
+    {
+      section.insert(key.into(), value);
+    }
+  }
+}
+
 struct Section(&'static str);
 impl Section {
-  fn display(&self) {
+  fn display(&self, report: &mut Report) {
+    if report.json {
+      report.section = self.0.into();
+      report
+        .sections
+        .insert(self.0.into(), serde_json::Value::Object(Default::default()));
+      return;
+    }
     println!();
     println!("{}", self.0.yellow().bold());
   }
@@ -549,7 +611,18 @@ impl VersionBlock {
     self
   }
 
-  fn display(&self) {
+  fn display(&self, report: &mut Report) {
+    if report.json {
+      let optional = |value: &str| (!value.is_empty()).then(|| value.to_string());
+      report.insert(
+        &self.name,
+        serde_json::json!({
+          "version": optional(&self.version),
+          "latest": optional(&self.target_version),
+        }),
+      );
+      return;
+    }
     indent(self.indentation);
     print!("{} ", "›".cyan());
     print!("{}", self.name.bold());
@@ -592,7 +665,11 @@ impl InfoBlock {
     }
   }
 
-  fn display(&self) {
+  fn display(&self, report: &mut Report) {
+    if report.json {
+      report.insert(&self.key, self.value.clone().into());
+      return;
+    }
     indent(self.indentation);
     print!("{} ", "›".cyan());
     print!("{}", self.key.bold());
@@ -602,8 +679,17 @@ impl InfoBlock {
   }
 }
 
-pub fn command(_options: Options) -> Result<()> {
-  Section("Environment").display();
+pub fn command(options: Options) -> Result<()> {
+  let mut report = Report {
+    json: options.json,
+    ..Default::default()
+  };
+  if options.json {
+    // the labels must not contain color codes
+    colored::control::set_override(false);
+  }
+
+  Section("Environment").display(&mut report);
 
   let os_info = os_info::get();
   VersionBlock::new(
@@ -615,14 +701,21 @@ pub fn command(_options: Options) -> Result<()> {
       os_info.bitness()
     ),
   )
-  .display();
+  .display(&mut report);
 
   #[cfg(windows)]
   VersionBlock::new(
     "Webview2",
     webview2_version().unwrap_or_default().unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
+
+  #[cfg(target_os = "linux")]
+  VersionBlock::new(
+    "webkit2gtk",
+    webkit2gtk_version().unwrap_or_default().unwrap_or_default(),
+  )
+  .display(&mut report);
 
   #[cfg(windows)]
   {
@@ -630,10 +723,10 @@ pub fn command(_options: Options) -> Result<()> {
       .unwrap_or_default()
       .unwrap_or_default();
 
-    if build_tools.is_empty() {
-      InfoBlock::new("MSVC", "").display();
+    if report.json || build_tools.is_empty() {
+      InfoBlock::new("MSVC", build_tools.join(", ")).display(&mut report);
     } else {
-      InfoBlock::new("MSVC", "").display();
+      InfoBlock::new("MSVC", "").display(&mut report);
       for i in build_tools {
         indent(6);
         println!("{}", format!("{} {}", "-".cyan(), i));
@@ -665,7 +758,7 @@ pub fn command(_options: Options) -> Result<()> {
       .collect::<String>(),
   )
   .target_version(metadata.js_cli.node.replace(">= ", ""))
-  .display();
+  .display(&mut report);
 
   VersionBlock::new(
     "npm",
@@ -673,15 +766,15 @@ pub fn command(_options: Options) -> Result<()> {
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   VersionBlock::new(
     "pnpm",
     get_version("pnpm", &[])
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
-  VersionBlock::new("yarn", &yarn_version).display();
+  .display(&mut report);
+  VersionBlock::new("yarn", &yarn_version).display(&mut report);
   VersionBlock::new(
     "rustup",
     get_version("rustup", &[])
@@ -693,7 +786,7 @@ pub fn command(_options: Options) -> Result<()> {
       })
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   VersionBlock::new(
     "rustc",
     get_version("rustc", &[])
@@ -705,7 +798,7 @@ pub fn command(_options: Options) -> Result<()> {
       })
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   VersionBlock::new(
     "cargo",
     get_version("cargo", &[])
@@ -717,35 +810,29 @@ pub fn command(_options: Options) -> Result<()> {
       })
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   InfoBlock::new(
     "Rust toolchain",
     active_rust_toolchain()
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
-
-  Section("Packages").display();
+  .display(&mut report);
+  InfoBlock::new(
+    "Rust targets",
+    installed_rust_targets()
+      .unwrap_or_default()
+      .unwrap_or_default()
+      .join(", "),
+  )
+  .display(&mut report);
 
-  let mut package_manager = PackageManager::Npm;
-  if let Some(app_dir) = &app_dir {
-    let app_dir_entries = read_dir(app_dir)
-      .unwrap()
-      .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
-      .collect::<Vec<String>>();
-    package_manager = get_package_manager(&app_dir_entries)?;
-  }
+  Section("Packages").display(&mut report);
 
-  if package_manager == PackageManager::Yarn
-    && yarn_version
-      .chars()
-      .next()
-      .map(|c| c > '1')
-      .unwrap_or_default()
-  {
-    package_manager = PackageManager::Berry;
-  }
+  let package_manager = match &app_dir {
+    Some(app_dir) => app_package_manager(app_dir, &yarn_version)?,
+    None => PackageManager::Npm,
+  };
 
   VersionBlock::new(
     format!("{} {}", "@tauri-apps/cli", "[NPM]".dimmed()),
@@ -756,7 +843,7 @@ pub fn command(_options: Options) -> Result<()> {
       .unwrap_or_default()
       .unwrap_or_default(),
   )
-  .display();
+  .display(&mut report);
   if let Some(app_dir) = &app_dir {
     VersionBlock::new(
       format!("{} {}", "@tauri-apps/api", "[NPM]".dimmed()),
@@ -769,7 +856,7 @@ pub fn command(_options: Options) -> Result<()> {
         .unwrap_or_default()
         .unwrap_or_default(),
     )
-    .display();
+    .display(&mut report);
   }
 
   let hook = panic::take_hook();
@@ -813,15 +900,15 @@ pub fn command(_options: Options) -> Result<()> {
             version_suffix.unwrap_or_else(|| "".into())
           ),
         )
-        .display();
+        .display(&mut report);
       }
     }
   }
 
   if tauri_dir.is_some() || app_dir.is_some() {
-    Section("App").display();
+    Section("App").display(&mut report);
     if tauri_dir.is_some() {
-      if let Ok(config) = get_config(None) {
+      if let Ok(config) = get_config(&[]) {
         let config_guard = config.lock().unwrap();
         let config = config_guard.as_ref().unwrap();
         InfoBlock::new(
@@ -832,7 +919,7 @@ pub fn command(_options: Options) -> Result<()> {
             "build".to_string()
           },
         )
-        .display();
+        .display(&mut report);
         InfoBlock::new(
           "CSP",
           config
@@ -843,9 +930,9 @@ pub fn command(_options: Options) -> Result<()> {
             .map(|c| c.to_string())
             .unwrap_or_else(|| "unset".to_string()),
         )
-        .display();
-        InfoBlock::new("distDir", config.build.dist_dir.to_string()).display();
-        InfoBlock::new("devPath", config.build.dev_path.to_string()).display();
+        .display(&mut report);
+        InfoBlock::new("distDir", config.build.dist_dir.to_string()).display(&mut report);
+        InfoBlock::new("devPath", config.build.dev_path.to_string()).display(&mut report);
       }
     }
 
@@ -853,41 +940,76 @@ pub fn command(_options: Options) -> Result<()> {
       if let Ok(package_json) = read_to_string(app_dir.join("package.json")) {
         let (framework, bundler) = infer_framework(&package_json);
         if let Some(framework) = framework {
-          InfoBlock::new("framework", framework.to_string()).display();
+          InfoBlock::new("framework", framework.to_string()).display(&mut report);
         }
         if let Some(bundler) = bundler {
-          InfoBlock::new("bundler", bundler.to_string()).display();
+          InfoBlock::new("bundler", bundler.to_string()).display(&mut report);
         }
-      } else {
+      } else if !report.json {
         println!("package.json not found");
       }
     }
   }
 
   if let Some(app_dir) = app_dir {
-    Section("App directory structure").display();
+    Section("App directory structure").display(&mut report);
     let dirs = read_dir(app_dir)?
       .filter(|p| p.is_ok() && p.as_ref().unwrap().path().is_dir())
       .collect::<Vec<Result<std::fs::DirEntry, _>>>();
-    let dirs_len = dirs.len();
-    for (i, entry) in dirs.into_iter().enumerate() {
-      let entry = entry?;
-      let prefix = if i + 1 == dirs_len {
-        "└─".cyan()
-      } else {
-        "├─".cyan()
-      };
-      println!(
-        "  {} {}",
-        prefix,
-        entry.path().file_name().unwrap().to_string_lossy()
-      );
+    if report.json {
+      let mut names = Vec::new();
+      for entry in dirs {
+        names.push(entry?.file_name().to_string_lossy().into_owned());
+      }
+      report.insert("directories", names.into());
+    } else {
+      let dirs_len = dirs.len();
+      for (i, entry) in dirs.into_iter().enumerate() {
+        let entry = entry?;
+        let prefix = if i + 1 == dirs_len {
+          "└─".cyan()
+        } else {
+          "├─".cyan()
+        };
+        println!(
+          "  {} {}",
+          prefix,
+          entry.path().file_name().unwrap().to_string_lossy()
+        );
+      }
     }
   }
 
+  if report.json {
+    println!("{}", serde_json::to_string_pretty(&report.sections)?);
+  }
+
   Ok(())
 }
 
+/// Detects the package manager used by the app from its lock files and the installed yarn version.
+pub(crate) fn app_package_manager(
+  app_dir: &Path,
+  yarn_version: &str,
+) -> crate::Result<PackageManager> {
+  let app_dir_entries = read_dir(app_dir)?
+    .map(|e| e.unwrap().file_name().to_string_lossy().into_owned())
+    .collect::<Vec<String>>();
+  let package_manager = get_package_manager(&app_dir_entries)?;
+
+  if package_manager == PackageManager::Yarn
+    && yarn_version
+      .chars()
+      .next()
+      .map(|c| c > '1')
+      .unwrap_or_default()
+  {
+    Ok(PackageManager::Berry)
+  } else {
+    Ok(package_manager)
+  }
+}
+
 fn get_package_manager<T: AsRef<str>>(app_dir_entries: &[T]) -> crate::Result<PackageManager> {
   let mut use_npm = false;
   let mut use_pnpm = false;
@@ -25,6 +25,7 @@ use dialoguer::Input;
 use handlebars::{to_json, Handlebars};
 use include_dir::{include_dir, Dir};
 use log::warn;
+use tauri_bundler::register_template_helpers;
 
 const TEMPLATE_DIR: Dir<'_> = include_dir!("templates/app");
 const TAURI_CONF_TEMPLATE: &str = include_str!("../templates/tauri.conf.json");
@@ -38,6 +39,9 @@ pub struct Options {
   /// Force init to overwrite the src-tauri folder
   #[clap(short, long)]
   force: bool,
+  /// Print the changes to the src-tauri folder as a diff without writing them
+  #[clap(long)]
+  dry_run: bool,
   /// Enables logging
   #[clap(short, long)]
   log: bool,
@@ -158,7 +162,7 @@ pub fn command(mut options: Options) -> Result<()> {
   let template_target_path = PathBuf::from(&options.directory).join("src-tauri");
   let metadata = serde_json::from_str::<VersionMetadata>(include_str!("../metadata.json"))?;
 
-  if template_target_path.exists() && !options.force {
+  if template_target_path.exists() && !options.force && !options.dry_run {
     warn!(
       "Tauri dir ({:?}) not empty. Run `init --force` to overwrite.",
       template_target_path
@@ -182,8 +186,11 @@ pub fn command(mut options: Options) -> Result<()> {
       )
     };
 
-    let _ = remove_dir_all(&template_target_path);
-    let handlebars = Handlebars::new();
+    if !options.dry_run {
+      let _ = remove_dir_all(&template_target_path);
+    }
+    let mut handlebars = Handlebars::new();
+    register_template_helpers(&mut handlebars);
 
     let mut data = BTreeMap::new();
     data.insert("tauri_dep", to_json(tauri_dep));
@@ -272,8 +279,12 @@ pub fn command(mut options: Options) -> Result<()> {
       to_json(serde_json::to_string_pretty(&config).unwrap()),
     );
 
-    template::render(&handlebars, &data, &TEMPLATE_DIR, &options.directory)
-      .with_context(|| "failed to render Tauri template")?;
+    if options.dry_run {
+      template::render_diff(&handlebars, &data, &TEMPLATE_DIR, &options.directory)
+    } else {
+      template::render(&handlebars, &data, &TEMPLATE_DIR, &options.directory)
+    }
+    .with_context(|| "failed to render Tauri template")?;
   }
 
   Ok(())
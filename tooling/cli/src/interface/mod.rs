@@ -53,7 +53,8 @@ pub trait AppSettings {
       .bundle_settings(self.get_bundle_settings(config, &enabled_features)?)
       .binaries(self.get_binaries(config, &target)?)
       .project_out_directory(out_dir)
-      .target(target);
+      .target(target)
+      .msi_backend(options.msi_backend);
 
     if let Some(types) = package_types {
       settings_builder = settings_builder.package_types(types);
@@ -22,13 +22,14 @@ use anyhow::Context;
 #[cfg(target_os = "linux")]
 use heck::ToKebabCase;
 use ignore::gitignore::{Gitignore, GitignoreBuilder};
-use log::{debug, error, info};
+use log::{debug, error, info, warn};
 use notify::RecursiveMode;
 use notify_debouncer_mini::new_debouncer;
 use serde::Deserialize;
 use shared_child::SharedChild;
 use tauri_bundler::{
-  AppCategory, BundleBinary, BundleSettings, DebianSettings, MacOsSettings, PackageSettings,
+  AppCategory, BundleBinary, BundleSettings, ChecksumSettings, DebianSettings, DmgSettings,
+  FlatpakSettings, MacOsSettings, PackageSettings, PkgSettings, RpmSettings, SnapSettings,
   UpdaterSettings, WindowsSettings,
 };
 use tauri_utils::config::parse::is_configuration_file;
@@ -36,7 +37,7 @@ use tauri_utils::config::parse::is_configuration_file;
 use super::{AppSettings, ExitReason, Interface};
 use crate::helpers::{
   app_paths::{app_dir, tauri_dir},
-  config::{reload as reload_config, wix_settings, Config},
+  config::{msix_settings, nsis_settings, reload as reload_config, wix_settings, Config},
 };
 
 mod cargo_config;
@@ -51,9 +52,12 @@ pub struct Options {
   pub debug: bool,
   pub target: Option<String>,
   pub features: Option<Vec<String>>,
+  pub no_default_features: bool,
+  pub profile: Option<String>,
   pub args: Vec<String>,
-  pub config: Option<String>,
+  pub config: Vec<String>,
   pub no_watch: bool,
+  pub msi_backend: tauri_bundler::MsiBackend,
 }
 
 impl From<crate::build::Options> for Options {
@@ -63,9 +67,12 @@ impl From<crate::build::Options> for Options {
       debug: options.debug,
       target: options.target,
       features: options.features,
+      no_default_features: options.no_default_features,
+      profile: options.profile,
       args: options.args,
       config: options.config,
       no_watch: true,
+      msi_backend: options.msi_backend.into(),
     }
   }
 }
@@ -77,9 +84,12 @@ impl From<crate::dev::Options> for Options {
       debug: !options.release_mode,
       target: options.target,
       features: options.features,
+      no_default_features: options.no_default_features,
+      profile: options.profile,
       args: options.args,
       config: options.config,
       no_watch: options.no_watch,
+      msi_backend: Default::default(),
     }
   }
 }
@@ -270,6 +280,24 @@ impl IgnoreMatcher {
   }
 }
 
+/// The delay used to batch file changes before rebuilding the app,
+/// configurable in milliseconds via the `TAURI_DEV_WATCHER_DEBOUNCE` environment variable.
+fn dev_watcher_debounce() -> Duration {
+  std::env::var("TAURI_DEV_WATCHER_DEBOUNCE")
+    .ok()
+    .and_then(|debounce| match debounce.parse() {
+      Ok(millis) => Some(Duration::from_millis(millis)),
+      Err(_) => {
+        warn!(
+          "invalid TAURI_DEV_WATCHER_DEBOUNCE value `{}`, expected a number of milliseconds",
+          debounce
+        );
+        None
+      }
+    })
+    .unwrap_or_else(|| Duration::from_secs(1))
+}
+
 fn build_ignore_matcher(dir: &Path) -> IgnoreMatcher {
   let mut matchers = Vec::new();
 
@@ -424,7 +452,7 @@ impl Rust {
     let common_ancestor = common_path::common_path_all(watch_folders.clone()).unwrap();
     let ignore_matcher = build_ignore_matcher(&common_ancestor);
 
-    let mut watcher = new_debouncer(Duration::from_secs(1), None, move |r| {
+    let mut watcher = new_debouncer(dev_watcher_debounce(), None, move |r| {
       if let Ok(events) = r {
         tx.send(events).unwrap()
       }
@@ -457,7 +485,7 @@ impl Rust {
 
           if !ignore_matcher.is_ignore(&event_path, event_path.is_dir()) {
             if is_configuration_file(&event_path) {
-              match reload_config(options.config.as_deref()) {
+              match reload_config(&options.config) {
                 Ok(config) => {
                   info!("Tauri configuration changed. Rewriting manifest...");
                   self.app_settings.manifest =
@@ -515,6 +543,13 @@ struct BinarySettings {
   path: Option<String>,
 }
 
+/// A `[profile.<name>]` section of the Cargo.toml.
+#[derive(Clone, Debug, Deserialize)]
+struct ProfileSettings {
+  /// the profile this custom profile is based on.
+  inherits: Option<String>,
+}
+
 /// The package settings.
 #[derive(Debug, Clone, Deserialize)]
 pub struct CargoPackageSettings {
@@ -545,6 +580,8 @@ struct CargoSettings {
   workspace: Option<WorkspaceSettings>,
   /// the binary targets configuration.
   bin: Option<Vec<BinarySettings>>,
+  /// the cargo profiles.
+  profile: Option<HashMap<String, ProfileSettings>>,
 }
 
 impl CargoSettings {
@@ -598,7 +635,7 @@ impl AppSettings for RustAppSettings {
       .expect("Cargo manifest must have the `package.name` field");
 
     let out_dir = self
-      .out_dir(options.target.clone(), options.debug)
+      .out_dir(options)
       .with_context(|| "failed to get project out directory")?;
 
     let binary_extension: String = if self.target_triple.contains("windows") {
@@ -785,12 +822,35 @@ impl RustAppSettings {
     &self.cargo_package_settings
   }
 
-  pub fn out_dir(&self, target: Option<String>, debug: bool) -> crate::Result<PathBuf> {
+  /// The names of the binaries cargo builds for the app: the package binary,
+  /// the `[[bin]]` targets and the `src/bin` files.
+  pub fn cargo_binary_names(&self) -> Vec<String> {
+    let mut names = Vec::new();
+    if let Some(name) = &self.cargo_package_settings.name {
+      names.push(name.clone());
+    }
+    if let Some(bin) = &self.cargo_settings.bin {
+      names.extend(bin.iter().map(|binary| binary.name.clone()));
+    }
+    if let Ok(fs_bins) = std::fs::read_dir(tauri_dir().join("src/bin")) {
+      for entry in fs_bins.flatten() {
+        if let Some(name) = entry.path().file_stem() {
+          names.push(name.to_string_lossy().into_owned());
+        }
+      }
+    }
+    names.sort();
+    names.dedup();
+    names
+  }
+
+  pub fn out_dir(&self, options: &Options) -> crate::Result<PathBuf> {
     get_target_dir(
-      target
+      options
+        .target
         .as_deref()
         .or_else(|| self.cargo_config.build().target()),
-      !debug,
+      profile_dir(options.profile.as_deref(), options.debug),
     )
   }
 }
@@ -799,6 +859,13 @@ impl RustAppSettings {
 struct CargoMetadata {
   target_directory: PathBuf,
   workspace_root: PathBuf,
+  packages: Vec<CargoMetadataPackage>,
+}
+
+#[derive(Deserialize)]
+struct CargoMetadataPackage {
+  version: String,
+  manifest_path: PathBuf,
 }
 
 fn get_cargo_metadata() -> crate::Result<CargoMetadata> {
@@ -817,9 +884,62 @@ fn get_cargo_metadata() -> crate::Result<CargoMetadata> {
   Ok(serde_json::from_slice(&output.stdout)?)
 }
 
-/// This function determines the 'target' directory and suffixes it with 'release' or 'debug'
+/// The name of the directory where cargo writes the artifacts of a profile.
+fn profile_dir(profile: Option<&str>, debug: bool) -> &str {
+  match profile {
+    Some("dev") | Some("test") => "debug",
+    Some("release") | Some("bench") => "release",
+    Some(profile) => profile,
+    None if debug => "debug",
+    None => "release",
+  }
+}
+
+/// Whether the given cargo profile builds in debug mode.
+///
+/// Custom profiles are resolved through their `inherits` key on the workspace Cargo.toml.
+pub fn is_debug_profile(profile: &str) -> crate::Result<bool> {
+  let workspace_dir = get_workspace_dir()?;
+  let cargo_settings =
+    CargoSettings::load(&workspace_dir).with_context(|| "failed to load cargo settings")?;
+  profile_is_debug(&cargo_settings, profile)
+}
+
+fn profile_is_debug<'a>(
+  cargo_settings: &'a CargoSettings,
+  mut profile: &'a str,
+) -> crate::Result<bool> {
+  let mut visited = Vec::new();
+  loop {
+    match profile {
+      "dev" | "test" => return Ok(true),
+      "release" | "bench" => return Ok(false),
+      _ => {}
+    }
+    if visited.contains(&profile) {
+      return Err(anyhow::anyhow!(
+        "the `{}` profile inherits from itself",
+        profile
+      ));
+    }
+    visited.push(profile);
+    profile = cargo_settings
+      .profile
+      .as_ref()
+      .and_then(|profiles| profiles.get(profile))
+      .and_then(|settings| settings.inherits.as_deref())
+      .ok_or_else(|| {
+        anyhow::anyhow!(
+          "the `{}` profile must be defined with an `inherits` key on the workspace Cargo.toml",
+          profile
+        )
+      })?;
+  }
+}
+
+/// This function determines the 'target' directory and suffixes it with the profile directory
 /// to determine where the compiled binary will be located.
-fn get_target_dir(target: Option<&str>, is_release: bool) -> crate::Result<PathBuf> {
+fn get_target_dir(target: Option<&str>, profile_dir: &str) -> crate::Result<PathBuf> {
   let mut path = get_cargo_metadata()
     .with_context(|| "failed to get cargo metadata")?
     .target_directory;
@@ -828,7 +948,7 @@ fn get_target_dir(target: Option<&str>, is_release: bool) -> crate::Result<PathB
     path.push(triple);
   }
 
-  path.push(if is_release { "release" } else { "debug" });
+  path.push(profile_dir);
 
   Ok(path)
 }
@@ -842,6 +962,27 @@ pub fn get_workspace_dir() -> crate::Result<PathBuf> {
   )
 }
 
+/// Executes `cargo metadata` to get the resolved version of the package in the given directory,
+/// which also covers versions inherited from the workspace.
+pub fn get_package_version(package_dir: &Path) -> crate::Result<Option<String>> {
+  let manifest_path = package_dir.join("Cargo.toml");
+  let manifest_path = manifest_path.canonicalize().unwrap_or(manifest_path);
+  Ok(
+    get_cargo_metadata()
+      .with_context(|| "failed to get cargo metadata")?
+      .packages
+      .into_iter()
+      .find(|package| {
+        package
+          .manifest_path
+          .canonicalize()
+          .unwrap_or(package.manifest_path.clone())
+          == manifest_path
+      })
+      .map(|package| package.version),
+  )
+}
+
 #[allow(unused_variables)]
 fn tauri_config_to_bundle_settings(
   manifest: &Manifest,
@@ -861,8 +1002,14 @@ fn tauri_config_to_bundle_settings(
       .cloned()
       .expect("the bundle config must have a `.ico` icon"),
   );
+  // only required by the wixl MSI backend outside of Windows
   #[cfg(not(windows))]
-  let windows_icon_path = PathBuf::from("");
+  let windows_icon_path = config
+    .icon
+    .iter()
+    .find(|i| i.ends_with(".ico"))
+    .map(PathBuf::from)
+    .unwrap_or_default();
 
   #[allow(unused_mut)]
   let mut resources = config.resources.unwrap_or_default();
@@ -916,6 +1063,16 @@ fn tauri_config_to_bundle_settings(
     None => config.macos.provider_short_name,
   };
 
+  let gpg_key_id = match std::env::var_os("TAURI_GPG_KEY_ID") {
+    Some(key_id) => Some(
+      key_id
+        .to_str()
+        .expect("failed to convert TAURI_GPG_KEY_ID to string")
+        .to_string(),
+    ),
+    None => config.gpg.key_id,
+  };
+
   Ok(BundleSettings {
     identifier: Some(config.identifier),
     publisher: config.publisher,
@@ -925,6 +1082,7 @@ fn tauri_config_to_bundle_settings(
     } else {
       Some(resources)
     },
+    exclude_resources: config.exclude_resources,
     copyright: config.copyright,
     category: match config.category {
       Some(category) => Some(AppCategory::from_str(&category).map_err(|e| match e {
@@ -936,6 +1094,10 @@ fn tauri_config_to_bundle_settings(
     short_description: config.short_description,
     long_description: config.long_description,
     external_bin: config.external_bin,
+    file_associations: config.file_associations,
+    deep_link_schemes: config.deep_link.schemes,
+    service: config.service,
+    gpg_key_id,
     deb: DebianSettings {
       depends: if depends.is_empty() {
         None
@@ -943,6 +1105,45 @@ fn tauri_config_to_bundle_settings(
         Some(depends)
       },
       files: config.deb.files,
+      maintainer: config.deb.maintainer,
+      section: config.deb.section,
+      priority: config.deb.priority,
+    },
+    rpm: RpmSettings {
+      release: config.rpm.release,
+      license: config.rpm.license,
+      depends: config.rpm.depends,
+      post_install_script: config.rpm.post_install_script,
+      post_remove_script: config.rpm.post_remove_script,
+    },
+    flatpak: FlatpakSettings {
+      runtime: config.flatpak.runtime,
+      runtime_version: config.flatpak.runtime_version,
+      sdk: config.flatpak.sdk,
+      finish_args: config.flatpak.finish_args,
+      license: config.flatpak.license,
+      build_bundle: config.flatpak.build_bundle,
+    },
+    snap: SnapSettings {
+      confinement: config.snap.confinement,
+      plugs: config.snap.plugs,
+      base: config.snap.base,
+      stage_packages: config.snap.stage_packages,
+      build_snap: config.snap.build_snap,
+    },
+    dmg: DmgSettings {
+      volume_name: config.dmg.volume_name,
+      background: config.dmg.background,
+      window_position: config.dmg.window_position,
+      window_size: config.dmg.window_size,
+      app_position: config.dmg.app_position,
+      application_folder_position: config.dmg.application_folder_position,
+    },
+    pkg: PkgSettings {
+      install_location: config.pkg.install_location,
+      pre_install_script: config.pkg.pre_install_script,
+      post_install_script: config.pkg.post_install_script,
+      signing_identity: config.pkg.signing_identity,
     },
     macos: MacOsSettings {
       frameworks: config.macos.frameworks,
@@ -966,16 +1167,26 @@ fn tauri_config_to_bundle_settings(
       tsp: config.windows.tsp,
       digest_algorithm: config.windows.digest_algorithm,
       certificate_thumbprint: config.windows.certificate_thumbprint,
+      certificate_path: config.windows.certificate_path.map(|p| tauri_dir().join(p)),
+      certificate_password: std::env::var("TAURI_WINDOWS_CERTIFICATE_PASSWORD").ok(),
       wix: config.windows.wix.map(|w| {
         let mut wix = wix_settings(w);
         wix.license = wix.license.map(|l| tauri_dir().join(l));
         wix
       }),
+      nsis: config.windows.nsis.map(nsis_settings),
+      msix: config.windows.msix.map(msix_settings),
       icon_path: windows_icon_path,
       webview_install_mode: config.windows.webview_install_mode,
       webview_fixed_runtime_path: config.windows.webview_fixed_runtime_path,
       allow_downgrades: config.windows.allow_downgrades,
+      install_scope: config.windows.install_scope,
     },
+    checksums: ChecksumSettings {
+      sha256_sums: config.checksums.sha256_sums,
+      individual: config.checksums.individual,
+    },
+    template_vars: config.template.vars,
     updater: Some(UpdaterSettings {
       active: updater_config.active,
       // we set it to true by default we shouldn't have to use
@@ -990,3 +1201,52 @@ fn tauri_config_to_bundle_settings(
     ..Default::default()
   })
 }
+
+#[cfg(test)]
+mod tests {
+  use super::{profile_dir, profile_is_debug, CargoSettings};
+
+  #[test]
+  fn profile_dir_names() {
+    assert_eq!(profile_dir(None, false), "release");
+    assert_eq!(profile_dir(None, true), "debug");
+    assert_eq!(profile_dir(Some("dev"), false), "debug");
+    assert_eq!(profile_dir(Some("test"), false), "debug");
+    assert_eq!(profile_dir(Some("release"), false), "release");
+    assert_eq!(profile_dir(Some("bench"), false), "release");
+    assert_eq!(profile_dir(Some("release-lto"), false), "release-lto");
+  }
+
+  #[test]
+  fn resolves_debug_profiles() {
+    let cargo_settings: CargoSettings = toml::from_str(
+      r#"
+        [profile.dev-opt]
+        inherits = "dev"
+
+        [profile.dist]
+        inherits = "release-lto"
+
+        [profile.release-lto]
+        inherits = "release"
+
+        [profile.broken]
+        opt-level = 3
+
+        [profile.cycle]
+        inherits = "cycle"
+      "#,
+    )
+    .unwrap();
+
+    assert!(profile_is_debug(&cargo_settings, "dev").unwrap());
+    assert!(profile_is_debug(&cargo_settings, "test").unwrap());
+    assert!(!profile_is_debug(&cargo_settings, "release").unwrap());
+    assert!(!profile_is_debug(&cargo_settings, "bench").unwrap());
+    assert!(profile_is_debug(&cargo_settings, "dev-opt").unwrap());
+    assert!(!profile_is_debug(&cargo_settings, "dist").unwrap());
+    assert!(profile_is_debug(&cargo_settings, "broken").is_err());
+    assert!(profile_is_debug(&cargo_settings, "cycle").is_err());
+    assert!(profile_is_debug(&cargo_settings, "missing").is_err());
+  }
+}
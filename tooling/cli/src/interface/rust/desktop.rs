@@ -89,8 +89,6 @@ pub fn build(
   let bin_path = app_settings.app_binary_path(&options)?;
   let out_dir = bin_path.parent().unwrap();
 
-  let bin_name = bin_path.file_stem().unwrap();
-
   if !std::env::var("STATIC_VCRUNTIME").map_or(false, |v| v == "false") {
     std::env::set_var("STATIC_VCRUNTIME", "true");
   }
@@ -98,31 +96,46 @@ pub fn build(
   if options.target == Some("universal-apple-darwin".into()) {
     std::fs::create_dir_all(out_dir).with_context(|| "failed to create project out directory")?;
 
-    let mut lipo_cmd = Command::new("lipo");
-    lipo_cmd
-      .arg("-create")
-      .arg("-output")
-      .arg(out_dir.join(bin_name));
+    let mut triple_out_dirs = Vec::new();
     for triple in ["aarch64-apple-darwin", "x86_64-apple-darwin"] {
       let mut options = options.clone();
       options.target.replace(triple.into());
 
       let triple_out_dir = app_settings
-        .out_dir(Some(triple.into()), options.debug)
+        .out_dir(&options)
         .with_context(|| format!("failed to get {} out dir", triple))?;
 
       build_production_app(options, available_targets, config_features.clone())
         .with_context(|| format!("failed to build {} binary", triple))?;
 
-      lipo_cmd.arg(triple_out_dir.join(bin_name));
+      triple_out_dirs.push(triple_out_dir);
     }
 
-    let lipo_status = lipo_cmd.output_ok()?.status;
-    if !lipo_status.success() {
-      return Err(anyhow::anyhow!(format!(
-        "Result of `lipo` command was unsuccessful: {}. (Is `lipo` installed?)",
-        lipo_status
-      )));
+    // merge every binary built for both architectures, including the ones declared as additional `[[bin]]` targets
+    for file_name in app_settings.cargo_binary_names() {
+      if !triple_out_dirs
+        .iter()
+        .all(|triple_out_dir| triple_out_dir.join(&file_name).is_file())
+      {
+        continue;
+      }
+
+      let mut lipo_cmd = Command::new("lipo");
+      lipo_cmd
+        .arg("-create")
+        .arg("-output")
+        .arg(out_dir.join(&file_name));
+      for triple_out_dir in &triple_out_dirs {
+        lipo_cmd.arg(triple_out_dir.join(&file_name));
+      }
+
+      let lipo_status = lipo_cmd.output_ok()?.status;
+      if !lipo_status.success() {
+        return Err(anyhow::anyhow!(format!(
+          "Result of `lipo` command was unsuccessful: {}. (Is `lipo` installed?)",
+          lipo_status
+        )));
+      }
     }
   } else {
     build_production_app(options, available_targets, config_features)
@@ -279,7 +292,14 @@ fn build_command(
     args.push(features.join(","));
   }
 
-  if !options.debug {
+  if options.no_default_features {
+    args.push("--no-default-features".into());
+  }
+
+  if let Some(profile) = options.profile {
+    args.push("--profile".into());
+    args.push(profile);
+  } else if !options.debug {
     args.push("--release".into());
   }
 
@@ -5,12 +5,16 @@
 pub use anyhow::Result;
 
 mod build;
+mod completions;
+mod config;
+mod deps;
 mod dev;
 mod helpers;
 mod icon;
 mod info;
 mod init;
 mod interface;
+mod migrate;
 mod plugin;
 mod signer;
 
@@ -55,6 +59,9 @@ struct Cli {
   /// Enables verbose logging
   #[clap(short, long, global = true, action = ArgAction::Count)]
   verbose: u8,
+  /// Path of the Tauri project, used instead of the current directory to find it
+  #[clap(long, global = true)]
+  project_path: Option<std::path::PathBuf>,
   #[clap(subcommand)]
   command: Commands,
 }
@@ -62,10 +69,14 @@ struct Cli {
 #[derive(Subcommand)]
 enum Commands {
   Build(build::Options),
+  Completions(completions::Options),
+  Config(config::Cli),
+  Deps(deps::Cli),
   Dev(dev::Options),
   Icon(icon::Options),
   Info(info::Options),
   Init(init::Options),
+  Migrate(migrate::Options),
   Plugin(plugin::Cli),
   Signer(signer::Cli),
 }
@@ -106,11 +117,11 @@ where
   I: IntoIterator<Item = A>,
   A: Into<OsString> + Clone,
 {
-  let matches = match bin_name {
+  let cli_command = match bin_name {
     Some(bin_name) => Cli::command().bin_name(bin_name),
     None => Cli::command(),
-  }
-  .get_matches_from(args);
+  };
+  let matches = cli_command.clone().get_matches_from(args);
 
   let res = Cli::from_arg_matches(&matches).map_err(format_error::<Cli>);
   let cli = match res {
@@ -118,11 +129,32 @@ where
     Err(e) => e.exit(),
   };
 
+  let json_output = matches!(
+    &cli.command,
+    Commands::Build(options) if options.format == build::OutputFormat::Json
+  );
+
   let mut builder = Builder::from_default_env();
   let init_res = builder
     .format_indent(Some(12))
     .filter(None, verbosity_level(cli.verbose).to_level_filter())
-    .format(|f, record| {
+    .format(move |f, record| {
+      if json_output {
+        let action = record
+          .key_values()
+          .get("action".into())
+          .map(|action| action.to_string());
+        let event = serde_json::json!({
+          "event": "log",
+          "level": prettyprint_level(record.level()).to_lowercase(),
+          "action": action,
+          "target": record.target(),
+          "thread": std::thread::current().name(),
+          "message": record.args().to_string(),
+        });
+        return writeln!(f, "{}", event);
+      }
+
       let mut is_command_output = false;
       if let Some(action) = record.key_values().get("action".into()) {
         let action = action.to_str().unwrap();
@@ -144,6 +176,14 @@ where
         )?;
       }
 
+      // the bundler builds the package types concurrently on threads named after them
+      if let Some(name) = std::thread::current().name().filter(|name| *name != "main") {
+        let mut name_style = f.style();
+        name_style.set_color(Color::Cyan);
+
+        write!(f, "{} ", name_style.value(format!("[{}]", name)))?;
+      }
+
       if !is_command_output && log_enabled!(Level::Debug) {
         let mut target_style = f.style();
         target_style.set_color(Color::Black);
@@ -159,12 +199,20 @@ where
     eprintln!("Failed to attach logger: {}", err);
   }
 
+  if let Some(project_path) = cli.project_path {
+    helpers::app_paths::set_project_path(project_path);
+  }
+
   match cli.command {
     Commands::Build(options) => build::command(options)?,
+    Commands::Completions(options) => completions::command(options, cli_command)?,
+    Commands::Config(cli) => config::command(cli)?,
+    Commands::Deps(cli) => deps::command(cli)?,
     Commands::Dev(options) => dev::command(options)?,
     Commands::Icon(options) => icon::command(options)?,
     Commands::Info(options) => info::command(options)?,
     Commands::Init(options) => init::command(options)?,
+    Commands::Migrate(options) => migrate::command(options)?,
     Commands::Plugin(cli) => plugin::command(cli)?,
     Commands::Signer(cli) => signer::command(cli)?,
   }
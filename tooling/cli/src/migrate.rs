@@ -0,0 +1,267 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::{helpers::app_paths::tauri_dir, Result, VersionMetadata};
+use anyhow::Context;
+use clap::Parser;
+use log::{info, warn};
+use semver::{Version, VersionReq};
+use serde_json::Value as JsonValue;
+use toml_edit::{Document, Item};
+
+use std::{
+  fmt,
+  fs::{read_to_string, write},
+  path::{Path, PathBuf},
+};
+
+/// Configuration keys that were renamed: the path of the object holding the key, its old and new names.
+const RENAMED_KEYS: &[(&[&str], &str, &str)] = &[(
+  &["tauri", "allowlist", "process"],
+  "relaunchDangerousAllowSymlinkMacOS",
+  "relaunchDangerousAllowSymlinkMacos",
+)];
+
+/// The deprecated names of `tauri > bundle > windows > webviewFixedRuntimePath`.
+const WEBVIEW_FIXED_RUNTIME_PATH_KEYS: &[&str] =
+  &["webviewFixedRuntimePath", "webview-fixed-runtime-path"];
+
+#[derive(Debug, Parser)]
+#[clap(about = "Migrate the configuration and dependencies to the current Tauri version")]
+pub struct Options {
+  /// Print the changes without writing them
+  #[clap(long)]
+  dry_run: bool,
+}
+
+/// A change that the migration can't apply.
+struct ManualStep {
+  path: PathBuf,
+  line: Option<usize>,
+  message: String,
+}
+
+impl fmt::Display for ManualStep {
+  fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+    write!(f, "{}", self.path.display())?;
+    if let Some(line) = self.line {
+      write!(f, ":{}", line)?;
+    }
+    write!(f, ": {}", self.message)
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let tauri_dir = tauri_dir();
+  let mut manual_steps = Vec::new();
+
+  migrate_config_files(&tauri_dir, options.dry_run, &mut manual_steps)?;
+  migrate_manifest(&tauri_dir, options.dry_run, &mut manual_steps)?;
+
+  if manual_steps.is_empty() {
+    info!("No manual migration steps required.");
+  } else {
+    warn!("The following changes must be applied manually:");
+    for step in manual_steps {
+      warn!("{}", step);
+    }
+  }
+
+  Ok(())
+}
+
+fn migrate_config_files(
+  tauri_dir: &Path,
+  dry_run: bool,
+  manual_steps: &mut Vec<ManualStep>,
+) -> Result<()> {
+  for platform in ["", "linux.", "windows.", "macos."] {
+    let json_path = tauri_dir.join(format!("tauri.{}conf.json", platform));
+    if json_path.exists() {
+      let config = read_to_string(&json_path)
+        .with_context(|| format!("failed to read {}", json_path.display()))?;
+      let mut config: JsonValue = serde_json::from_str(&config)
+        .with_context(|| format!("failed to parse {}", json_path.display()))?;
+      let changes = migrate_config(&mut config);
+      if !changes.is_empty() {
+        for change in &changes {
+          info!(action = "Migrating"; "{}: {}", json_path.display(), change);
+        }
+        if !dry_run {
+          write(
+            &json_path,
+            format!("{}\n", serde_json::to_string_pretty(&config)?),
+          )
+          .with_context(|| format!("failed to write {}", json_path.display()))?;
+        }
+      }
+    }
+
+    // the other formats are not rewritten so their comments and layout are preserved
+    for path in [
+      tauri_dir.join(format!("tauri.{}conf.json5", platform)),
+      tauri_dir.join(format!("Tauri.{}toml", platform)),
+    ] {
+      if let Ok(config) = read_to_string(&path) {
+        for (_, old, new) in RENAMED_KEYS {
+          if let Some(line) = find_line(&config, |line| line.contains(old)) {
+            manual_steps.push(ManualStep {
+              path: path.clone(),
+              line: Some(line),
+              message: format!("rename `{}` to `{}`", old, new),
+            });
+          }
+        }
+        for old in WEBVIEW_FIXED_RUNTIME_PATH_KEYS {
+          if let Some(line) = find_line(&config, |line| line.contains(old)) {
+            manual_steps.push(ManualStep {
+              path: path.clone(),
+              line: Some(line),
+              message: format!(
+                "replace `{}` with `webviewInstallMode` set to `{{ \"type\": \"fixedRuntime\", \"path\": ... }}`",
+                old
+              ),
+            });
+          }
+        }
+      }
+    }
+  }
+  Ok(())
+}
+
+/// Applies the configuration migrations, returning a description of each change.
+fn migrate_config(config: &mut JsonValue) -> Vec<String> {
+  let mut changes = Vec::new();
+
+  for (path, old, new) in RENAMED_KEYS {
+    let pointer = format!("/{}", path.join("/"));
+    if let Some(object) = config.pointer_mut(&pointer).and_then(|o| o.as_object_mut()) {
+      if let Some(value) = object.remove(*old) {
+        object.insert(new.to_string(), value);
+        changes.push(format!(
+          "renamed `{} > {}` to `{}`",
+          path.join(" > "),
+          old,
+          new
+        ));
+      }
+    }
+  }
+
+  if let Some(windows) = config
+    .pointer_mut("/tauri/bundle/windows")
+    .and_then(|o| o.as_object_mut())
+  {
+    for old in WEBVIEW_FIXED_RUNTIME_PATH_KEYS {
+      if let Some(path) = windows.remove(*old) {
+        if !path.is_null() {
+          windows.insert(
+            "webviewInstallMode".into(),
+            serde_json::json!({ "type": "fixedRuntime", "path": path }),
+          );
+        }
+        changes.push(format!(
+          "replaced `tauri > bundle > windows > {}` with `webviewInstallMode`",
+          old
+        ));
+      }
+    }
+  }
+
+  changes
+}
+
+fn migrate_manifest(
+  tauri_dir: &Path,
+  dry_run: bool,
+  manual_steps: &mut Vec<ManualStep>,
+) -> Result<()> {
+  let metadata = serde_json::from_str::<VersionMetadata>(include_str!("../metadata.json"))?;
+  let manifest_path = tauri_dir.join("Cargo.toml");
+  let manifest_str = read_to_string(&manifest_path)
+    .with_context(|| format!("failed to read {}", manifest_path.display()))?;
+  let mut manifest: Document = manifest_str
+    .parse()
+    .with_context(|| format!("failed to parse {}", manifest_path.display()))?;
+  let mut changed = false;
+
+  for (table, name, version) in [
+    ("dependencies", "tauri", &metadata.tauri),
+    ("build-dependencies", "tauri-build", &metadata.tauri_build),
+  ] {
+    let latest = Version::parse(version)?;
+    let dependency = match manifest
+      .as_table_mut()
+      .get_mut(table)
+      .and_then(|t| t.get_mut(name))
+    {
+      Some(dependency) => dependency,
+      None => continue,
+    };
+    let line = find_line(&manifest_str, |line| {
+      line.trim() == format!("[{}.{}]", table, name)
+        || line
+          .trim_start()
+          .strip_prefix(name)
+          .map(|rest| rest.trim_start().starts_with('='))
+          .unwrap_or_default()
+    });
+
+    let requirement = if let Some(requirement) = dependency.as_str() {
+      Some(requirement.to_string())
+    } else {
+      dependency
+        .as_table_like()
+        .and_then(|t| t.get("version"))
+        .and_then(Item::as_str)
+        .map(ToString::to_string)
+    };
+
+    match requirement {
+      Some(requirement) => {
+        if VersionReq::parse(&requirement)
+          .map(|r| r.matches(&latest))
+          .unwrap_or_default()
+        {
+          continue;
+        }
+        info!(
+          action = "Migrating";
+          "{}: updated `{}` from `{}` to `{}`",
+          manifest_path.display(),
+          name,
+          requirement,
+          version
+        );
+        if dependency.is_str() {
+          *dependency = toml_edit::value(version.as_str());
+        } else {
+          dependency["version"] = toml_edit::value(version.as_str());
+        }
+        changed = true;
+      }
+      None => manual_steps.push(ManualStep {
+        path: manifest_path.clone(),
+        line,
+        message: format!(
+          "`{}` is not a crates.io dependency, make sure it points to version {}",
+          name, version
+        ),
+      }),
+    }
+  }
+
+  if changed && !dry_run {
+    write(&manifest_path, manifest.to_string())
+      .with_context(|| format!("failed to write {}", manifest_path.display()))?;
+  }
+
+  Ok(())
+}
+
+/// Finds the line number (starting at 1) of the first line matching the predicate.
+fn find_line<F: Fn(&str) -> bool>(content: &str, predicate: F) -> Option<usize> {
+  content.lines().position(predicate).map(|i| i + 1)
+}
@@ -14,6 +14,7 @@ use heck::{AsKebabCase, ToKebabCase, ToSnakeCase};
 use include_dir::{include_dir, Dir};
 use log::warn;
 use std::{collections::BTreeMap, env::current_dir, fs::remove_dir_all, path::PathBuf};
+use tauri_bundler::register_template_helpers;
 
 const BACKEND_PLUGIN_DIR: Dir<'_> = include_dir!("templates/plugin/backend");
 const API_PLUGIN_DIR: Dir<'_> = include_dir!("templates/plugin/with-api");
@@ -23,23 +24,23 @@ const API_PLUGIN_DIR: Dir<'_> = include_dir!("templates/plugin/with-api");
 pub struct Options {
   /// Name of your Tauri plugin
   #[clap(short = 'n', long = "name")]
-  plugin_name: String,
+  pub(crate) plugin_name: String,
   /// Initializes a Tauri plugin with TypeScript API
   #[clap(long)]
-  api: bool,
+  pub(crate) api: bool,
   /// Initializes a Tauri core plugin (internal usage)
   #[clap(long, hide(true))]
-  tauri: bool,
+  pub(crate) tauri: bool,
   /// Set target directory for init
   #[clap(short, long)]
   #[clap(default_value_t = current_dir().expect("failed to read cwd").display().to_string())]
-  directory: String,
+  pub(crate) directory: String,
   /// Path of the Tauri project to use (relative to the cwd)
   #[clap(short, long)]
-  tauri_path: Option<PathBuf>,
+  pub(crate) tauri_path: Option<PathBuf>,
   /// Author name
   #[clap(short, long)]
-  author: Option<String>,
+  pub(crate) author: Option<String>,
 }
 
 impl Options {
@@ -89,7 +90,8 @@ pub fn command(mut options: Options) -> Result<()> {
       };
 
     let _ = remove_dir_all(&template_target_path);
-    let handlebars = Handlebars::new();
+    let mut handlebars = Handlebars::new();
+    register_template_helpers(&mut handlebars);
 
     let mut data = BTreeMap::new();
     data.insert("plugin_name_original", to_json(&options.plugin_name));
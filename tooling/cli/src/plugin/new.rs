@@ -0,0 +1,51 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use crate::Result;
+use clap::Parser;
+use std::{env::current_dir, path::PathBuf};
+
+#[derive(Debug, Parser)]
+#[clap(about = "Creates a new Tauri plugin project")]
+pub struct Options {
+  /// Name of your Tauri plugin
+  plugin_name: String,
+  /// Initializes a Tauri plugin with TypeScript API
+  #[clap(long)]
+  api: bool,
+  /// Initializes a Tauri core plugin (internal usage)
+  #[clap(long, hide(true))]
+  tauri: bool,
+  /// Set the directory where the `tauri-plugin-{name}` project is created, defaults to the cwd
+  #[clap(short, long)]
+  directory: Option<String>,
+  /// Path of the Tauri project to use (relative to the cwd)
+  #[clap(short, long)]
+  tauri_path: Option<PathBuf>,
+  /// Author name
+  #[clap(short, long)]
+  author: Option<String>,
+}
+
+impl From<Options> for super::init::Options {
+  fn from(o: Options) -> Self {
+    Self {
+      plugin_name: o.plugin_name,
+      api: o.api,
+      tauri: o.tauri,
+      directory: o.directory.unwrap_or_else(|| {
+        current_dir()
+          .expect("failed to read cwd")
+          .display()
+          .to_string()
+      }),
+      tauri_path: o.tauri_path,
+      author: o.author,
+    }
+  }
+}
+
+pub fn command(options: Options) -> Result<()> {
+  super::init::command(options.into())
+}
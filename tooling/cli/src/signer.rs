@@ -7,6 +7,7 @@ use clap::{Parser, Subcommand};
 
 mod generate;
 mod sign;
+mod verify;
 
 #[derive(Parser)]
 #[clap(
@@ -25,12 +26,14 @@ pub struct Cli {
 enum Commands {
   Sign(sign::Options),
   Generate(generate::Options),
+  Verify(verify::Options),
 }
 
 pub fn command(cli: Cli) -> Result<()> {
   match cli.command {
     Commands::Sign(options) => sign::command(options)?,
     Commands::Generate(options) => generate::command(options)?,
+    Commands::Verify(options) => verify::command(options)?,
   }
   Ok(())
 }
@@ -0,0 +1,58 @@
+// Copyright 2019-2022 Tauri Programme within The Commons Conservancy
+// SPDX-License-Identifier: Apache-2.0
+// SPDX-License-Identifier: MIT
+
+use std::path::{Path, PathBuf};
+
+use crate::{
+  helpers::updater_signature::{public_key, read_key_from_file, verify_file},
+  Result,
+};
+use anyhow::Context;
+use clap::Parser;
+
+#[derive(Debug, Parser)]
+#[clap(about = "Verify the signature of a file")]
+pub struct Options {
+  /// Load the public key from a string
+  #[clap(short = 'k', long, conflicts_with("public-key-path"))]
+  public_key: Option<String>,
+  /// Load the public key from a file
+  #[clap(short = 'f', long, conflicts_with("public-key"))]
+  public_key_path: Option<PathBuf>,
+  /// Path to the signature file. Defaults to the file path with a `.sig` suffix
+  #[clap(short, long)]
+  signature: Option<PathBuf>,
+  /// Verify the specified file
+  file: PathBuf,
+}
+
+pub fn command(options: Options) -> Result<()> {
+  let public_key_string = if let Some(public_key_path) = options.public_key_path {
+    read_key_from_file(Path::new(&public_key_path)).with_context(|| "Unable to read public key")?
+  } else if let Some(public_key) = options.public_key {
+    public_key
+  } else {
+    return Err(anyhow::anyhow!(
+      "Verification aborted: Unable to find the public key".to_string(),
+    ));
+  };
+
+  let signature_path = options.signature.unwrap_or_else(|| {
+    let mut path = options.file.clone().into_os_string();
+    path.push(".sig");
+    path.into()
+  });
+  let signature = read_key_from_file(&signature_path)
+    .with_context(|| format!("Unable to read signature {}", signature_path.display()))?;
+
+  verify_file(
+    &public_key(public_key_string)?,
+    &options.file,
+    signature.trim().to_string(),
+  )?;
+
+  println!("\nThe signature of {} is valid.", options.file.display());
+
+  Ok(())
+}